@@ -0,0 +1,798 @@
+// src-tauri/src/bin/stackburn-cli.rs
+//! Headless StackBurn: runs the same scanners and burn score engine as the
+//! desktop app, printing JSON or Markdown to stdout.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use stackburn_lib::burn_score::{self, BurnScoreInput, BurnScoreResult};
+use stackburn_lib::cleanup::dedupe::{self, LinkMode};
+use stackburn_lib::cleanup::empty_dirs;
+use stackburn_lib::cleanup::plan::{self, CleanupPlan, PlanAction, PlanFormat, PlanOptions};
+use stackburn_lib::cleanup::quarantine::{self, Quarantine};
+use stackburn_lib::cleanup::trash::{self, Trash};
+use stackburn_lib::cleanup::Disposal;
+use stackburn_lib::history::{self, ScanHistory, ScanKind};
+use stackburn_lib::scanners::file_index::{self, FileIndex};
+use stackburn_lib::scanners::github_scanner::GitHubScanner;
+use stackburn_lib::scanners::local_scanner::{DuplicateScanResult, LocalScanner, ScanOptions};
+use stackburn_lib::scanners::progress::{ScanMonitor, ScanProgress};
+use stackburn_lib::scanners::rules::ScanRules;
+use stackburn_lib::scanners::similar_images::{ImageHashAlgorithm, ImageSimilarity};
+use stackburn_lib::scanners::similar_text::TextSimilarity;
+use stackburn_lib::scanners::size_tree::TreeOptions;
+use stackburn_lib::scanners::workers::{StorageKind, WorkerConfig};
+use stackburn_lib::scanners::{archives, github_scanner, local_scanner, ScannedItem, Scanner};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(
+    name = "stackburn-cli",
+    version,
+    about = "Scan your digital stack for bloat"
+)]
+struct Cli {
+    /// Scan history database [default: the desktop app's]
+    #[arg(long, global = true)]
+    history: Option<PathBuf>,
+    /// Don't record scans and scores in the history
+    #[arg(long, global = true)]
+    no_history: bool,
+    /// Print scan progress to stderr. Ctrl-C stops a scan and prints what it found so far.
+    #[arg(long, global = true)]
+    progress: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a scanner and print its result as JSON
+    Scan {
+        #[command(subcommand)]
+        source: ScanSource,
+    },
+    /// Calculate the burn score from saved scan results ("-" reads stdin)
+    Score {
+        /// FolderStats JSON from `scan local`
+        #[arg(long)]
+        local: Option<String>,
+        /// GitHubScanResult JSON from `scan github`
+        #[arg(long)]
+        github: Option<String>,
+        /// DriveScanResult JSON exported from the desktop app
+        #[arg(long)]
+        drive: Option<String>,
+        /// ArtifactScanResult JSON from `scan artifacts`
+        #[arg(long)]
+        artifacts: Option<String>,
+        /// Normalized items JSON from `scan items`
+        #[arg(long)]
+        items: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+    },
+    /// Render the Markdown report for a saved burn score ("-" reads stdin)
+    Report {
+        /// BurnScoreResult JSON from `score`
+        score: String,
+    },
+    /// Browse past scans and compare them
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Replace duplicate copies with links to the first copy, keeping every path ("-" reads stdin)
+    Dedupe {
+        /// DuplicateScanResult JSON from `scan duplicates`
+        result: String,
+        /// How to link copies (auto, hardlink, reflink); auto reflinks where the filesystem can
+        #[arg(long, default_value = "auto")]
+        mode: LinkMode,
+    },
+    /// Remove folders that hold nothing but empty folders, innermost first
+    PruneEmpty {
+        /// Folders to prune, such as the empty_directories from `scan local`
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Review cleanup actions in a plan file before applying them
+    Plan {
+        #[command(subcommand)]
+        action: PlanCommand,
+    },
+    /// Move files to the desktop trash and restore the ones StackBurn put there
+    Trash {
+        /// Record of trashed files [default: the desktop app's]
+        #[arg(long)]
+        log: Option<PathBuf>,
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// Move files somewhere they can be restored from until the retention period ends
+    Quarantine {
+        /// Quarantine directory [default: the desktop app's]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        #[command(subcommand)]
+        action: QuarantineAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlanCommand {
+    /// Propose cleanup actions from a saved scan or burn score ("-" reads stdin)
+    Create {
+        /// FolderStats JSON from `scan local`
+        #[arg(long, conflicts_with = "score", required_unless_present = "score")]
+        local: Option<String>,
+        /// BurnScoreResult JSON from `score`
+        #[arg(long)]
+        score: Option<String>,
+        /// What to do with redundant duplicate copies (delete, hardlink) [default: delete].
+        /// Local scans only, since a burn score doesn't say which copy is kept
+        #[arg(long, conflicts_with = "score")]
+        duplicates: Option<PlanAction>,
+        /// What to do with files unused for 6+ months (delete, move, archive)
+        #[arg(long)]
+        unused: Option<PlanAction>,
+        /// What to do with temp and cache files (delete, move, archive)
+        #[arg(long)]
+        temporary: Option<PlanAction>,
+        /// Where moved and archived files go
+        #[arg(long)]
+        destination: Option<String>,
+        /// Write the plan here; `.toml` files are written as TOML [default: JSON on stdout]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Apply a plan, skipping files that changed since it was made
+    Apply {
+        plan: PathBuf,
+        /// Only check the plan against the files and report drift
+        #[arg(long)]
+        dry_run: bool,
+        /// Quarantine directory for deleted files [default: the desktop app's]
+        #[arg(long, conflicts_with = "trash")]
+        quarantine: Option<PathBuf>,
+        /// Send deleted files to the desktop trash instead of the quarantine
+        #[arg(long)]
+        trash: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    /// Move files or folders to the trash
+    Add {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Trash every copy but the first in each group ("-" reads stdin)
+    Duplicates {
+        /// DuplicateScanResult JSON from `scan duplicates`
+        result: String,
+    },
+    /// List what StackBurn trashed that is still in the trash, newest first
+    List,
+    /// Move trashed items back, by their path inside the trash
+    Restore {
+        #[arg(required = true)]
+        trashed_paths: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum QuarantineAction {
+    /// Quarantine files as one batch
+    Add {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// Note stored with the batch
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Quarantine every copy but the first in each group ("-" reads stdin)
+    Duplicates {
+        /// DuplicateScanResult JSON from `scan duplicates`
+        result: String,
+    },
+    /// List quarantined batches, newest first
+    List,
+    /// Move a batch's files back where they came from
+    Restore { batch_id: String },
+    /// Permanently delete batches past the retention period
+    Purge {
+        /// Purge batches older than this instead
+        #[arg(long)]
+        older_than_days: Option<u32>,
+    },
+    /// Set how many days new batches are kept
+    Retention { days: u32 },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// List recent scans, newest first
+    List {
+        /// Only show one kind of scan (local, github, burn_score)
+        #[arg(long)]
+        kind: Option<ScanKind>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Print a stored scan with its full result
+    Show { id: i64 },
+    /// Show how size, item count and score changed between two scans
+    Diff { from: i64, to: i64 },
+}
+
+#[derive(Subcommand)]
+enum ScanSource {
+    /// Scan a local directory tree, rehashing only files changed since the last scan
+    Local {
+        path: String,
+        /// Rehash every file instead of reusing hashes and categories from the file index
+        #[arg(long)]
+        full: bool,
+        /// File index database [default: the desktop app's]
+        #[arg(long, conflicts_with = "no_index")]
+        index: Option<PathBuf>,
+        /// Don't read or update the file index
+        #[arg(long)]
+        no_index: bool,
+        /// Look inside zip and tar archives for contents already unpacked elsewhere
+        #[arg(long)]
+        archives: bool,
+        /// Also group resized and re-encoded copies of the same image
+        #[arg(long)]
+        similar_images: bool,
+        /// Image hash to compare with (average, difference, perceptual)
+        #[arg(long, default_value = "perceptual", requires = "similar_images")]
+        image_hash: ImageHashAlgorithm,
+        /// Share of hash bits, 0 to 1, two images must have in common to be grouped
+        #[arg(long, default_value_t = 0.9, requires = "similar_images")]
+        image_similarity: f64,
+        /// Also group text files, source code, CSVs and office documents that are mostly the same
+        #[arg(long)]
+        similar_text: bool,
+        /// Estimated share of content, 0 to 1, two files must have in common to be grouped
+        #[arg(long, default_value_t = 0.9, requires = "similar_text")]
+        text_similarity: f64,
+        /// Include folder sizes as a tree, with the bloat in each folder
+        #[arg(long)]
+        tree: bool,
+        /// Folder levels to include in the tree
+        #[arg(long, default_value_t = 2, requires = "tree")]
+        tree_depth: usize,
+        /// Fold folders smaller than this share of their parent, 0 to 1, into one entry
+        #[arg(long, default_value_t = 0.01, requires = "tree")]
+        tree_min_share: f64,
+        #[command(flatten)]
+        rules: RuleArgs,
+        #[command(flatten)]
+        workers: WorkerArgs,
+    },
+    /// Find duplicate files across one or more directories
+    Duplicates {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[command(flatten)]
+        rules: RuleArgs,
+        #[command(flatten)]
+        workers: WorkerArgs,
+    },
+    /// Find node_modules, target, build and dist folders and the projects that own them
+    Artifacts {
+        path: String,
+        #[command(flatten)]
+        workers: WorkerArgs,
+    },
+    /// List and hash the files inside a zip, tar, tar.gz, tar.zst or gz archive
+    Archive { path: PathBuf },
+    /// Scan every repository visible to a GitHub token
+    Github {
+        /// Personal access token
+        #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+        token: String,
+    },
+    /// Run several sources through the common scanner interface and print their items
+    Items {
+        /// Local directory to scan (repeatable)
+        #[arg(
+            long = "local",
+            value_name = "PATH",
+            required_unless_present = "github"
+        )]
+        local: Vec<String>,
+        /// Also scan the repositories visible to the GitHub token
+        #[arg(long)]
+        github: bool,
+        /// Personal access token used with --github
+        #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+        github_token: Option<String>,
+        #[command(flatten)]
+        rules: RuleArgs,
+        #[command(flatten)]
+        workers: WorkerArgs,
+    },
+}
+
+#[derive(Args)]
+struct RuleArgs {
+    /// Only keep files matching this gitignore-style pattern (repeatable)
+    #[arg(long = "include", value_name = "GLOB")]
+    include: Vec<String>,
+    /// Skip files and directories matching this pattern, on top of the defaults (repeatable)
+    #[arg(long = "exclude", value_name = "GLOB")]
+    exclude: Vec<String>,
+    /// Don't skip node_modules, .git, target, build, dist and system folders
+    #[arg(long)]
+    no_default_excludes: bool,
+    /// Honor .gitignore and .ignore files
+    #[arg(long)]
+    gitignore: bool,
+    /// Include dotfiles and dot-directories, e.g. ~/.cache
+    #[arg(long)]
+    hidden: bool,
+    #[arg(long)]
+    max_depth: Option<usize>,
+    /// Skip files smaller than this many bytes
+    #[arg(long)]
+    min_size: Option<u64>,
+    /// Skip files larger than this many bytes
+    #[arg(long)]
+    max_size: Option<u64>,
+    /// Walk into symlinked files and folders instead of skipping them
+    #[arg(long)]
+    follow_symlinks: bool,
+    /// Descend into other filesystems mounted below the scanned path
+    #[arg(long)]
+    cross_filesystems: bool,
+}
+
+impl From<RuleArgs> for ScanRules {
+    fn from(args: RuleArgs) -> Self {
+        let mut rules = ScanRules::default();
+        if args.no_default_excludes {
+            rules.exclude.clear();
+        }
+        rules.exclude.extend(args.exclude);
+        rules.include = args.include;
+        rules.respect_ignore_files = args.gitignore;
+        rules.include_hidden = args.hidden;
+        rules.max_depth = args.max_depth;
+        rules.min_size = args.min_size;
+        rules.max_size = args.max_size;
+        rules.follow_symlinks = args.follow_symlinks;
+        rules.cross_filesystems = args.cross_filesystems;
+        rules
+    }
+}
+
+#[derive(Args)]
+struct WorkerArgs {
+    /// Storage behind the scanned paths (auto, ssd, hdd); decides how many files are read at once
+    #[arg(long, default_value = "auto")]
+    storage: StorageKind,
+    /// Upper bound on walking and hashing threads
+    #[arg(long)]
+    workers: Option<usize>,
+}
+
+impl From<WorkerArgs> for WorkerConfig {
+    fn from(args: WorkerArgs) -> Self {
+        WorkerConfig {
+            storage: args.storage,
+            max_workers: args.workers,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Markdown,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(cli).await {
+        eprintln!("✗ {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let history = open_history(&cli)?;
+
+    match cli.command {
+        Command::Scan { source } => {
+            let monitor = scan_monitor(cli.progress);
+            match source {
+                ScanSource::Local {
+                    path,
+                    full,
+                    index,
+                    no_index,
+                    archives,
+                    similar_images,
+                    image_hash,
+                    image_similarity,
+                    similar_text,
+                    text_similarity,
+                    tree,
+                    tree_depth,
+                    tree_min_share,
+                    rules,
+                    workers,
+                } => {
+                    let index = if no_index {
+                        None
+                    } else {
+                        Some(open_store(
+                            index,
+                            file_index::FILE_INDEX_FILE_NAME,
+                            FileIndex::open,
+                        )?)
+                    };
+                    let options = ScanOptions {
+                        full_rescan: full,
+                        workers: workers.into(),
+                        rules: rules.into(),
+                        inspect_archives: archives,
+                        similar_images: similar_images.then_some(ImageSimilarity {
+                            algorithm: image_hash,
+                            threshold: image_similarity,
+                        }),
+                        similar_text: similar_text.then_some(TextSimilarity {
+                            threshold: text_similarity,
+                        }),
+                        tree: tree.then_some(TreeOptions {
+                            depth: tree_depth,
+                            min_share: tree_min_share,
+                        }),
+                    };
+                    let stats =
+                        local_scanner::scan_path(&path, index.as_ref(), None, &options, &monitor)
+                            .await?;
+                    if let Some(history) = history.as_ref().filter(|_| !stats.incomplete) {
+                        warn_on_error(history.record_local_scan(&path, &stats));
+                    }
+                    print_json(&stats)?;
+                    check_complete(stats.incomplete)
+                }
+                ScanSource::Duplicates {
+                    paths,
+                    rules,
+                    workers,
+                } => {
+                    let rules = rules.into();
+                    let result =
+                        local_scanner::scan_duplicates(paths, &rules, workers.into(), &monitor)
+                            .await?;
+                    print_json(&result)?;
+                    check_complete(result.incomplete)
+                }
+                ScanSource::Artifacts { path, workers } => {
+                    let result =
+                        local_scanner::scan_artifacts(&path, workers.into(), &monitor).await?;
+                    print_json(&result)?;
+                    check_complete(result.incomplete)
+                }
+                ScanSource::Archive { path } => print_json(&archives::list_archive(&path)?),
+                ScanSource::Github { token } => {
+                    let result =
+                        github_scanner::scan_repositories_with_token(&token, &monitor).await?;
+                    if let Some(history) = history.as_ref().filter(|_| !result.incomplete) {
+                        warn_on_error(history.record_github_scan(&result));
+                    }
+                    print_json(&result)?;
+                    check_complete(result.incomplete)
+                }
+                ScanSource::Items {
+                    local,
+                    github,
+                    github_token,
+                    rules,
+                    workers,
+                } => {
+                    let mut scanners: Vec<Box<dyn Scanner>> = Vec::new();
+                    if !local.is_empty() {
+                        scanners.push(Box::new(LocalScanner {
+                            roots: local,
+                            workers: workers.into(),
+                            rules: rules.into(),
+                        }));
+                    }
+                    if github {
+                        let token = github_token
+                            .ok_or("--github needs a token in GITHUB_TOKEN or --github-token")?;
+                        let mut scanner = GitHubScanner::default();
+                        scanner.authenticate(&token).await?;
+                        scanners.push(Box::new(scanner));
+                    }
+
+                    let mut items: Vec<ScannedItem> = Vec::new();
+                    for scanner in &scanners {
+                        let scanned = scanner
+                            .scan()
+                            .await
+                            .map_err(|e| format!("{}: {}", scanner.name(), e))?;
+                        items.extend(scanned);
+                    }
+                    print_json(&items)
+                }
+            }
+        }
+        Command::Score {
+            local,
+            github,
+            drive,
+            artifacts,
+            items,
+            format,
+        } => {
+            let input = BurnScoreInput {
+                drive_data: drive.as_deref().map(read_json).transpose()?,
+                local_data: local.as_deref().map(read_json).transpose()?,
+                github_data: github.as_deref().map(read_json).transpose()?,
+                artifact_data: artifacts.as_deref().map(read_json).transpose()?,
+                items: items
+                    .as_deref()
+                    .map(read_json)
+                    .transpose()?
+                    .unwrap_or_default(),
+            };
+            let result = burn_score::compute_burn_score(input)?;
+            if let Some(history) = &history {
+                warn_on_error(history.record_burn_score(&result));
+            }
+
+            match format {
+                OutputFormat::Json => print_json(&result),
+                OutputFormat::Markdown => {
+                    print!("{}", burn_score::generate_report(result).await?);
+                    Ok(())
+                }
+            }
+        }
+        Command::Report { score } => {
+            let result: BurnScoreResult = read_json(&score)?;
+            print!("{}", burn_score::generate_report(result).await?);
+            Ok(())
+        }
+        Command::History { action } => {
+            let history = history.ok_or("Scan history is disabled")?;
+
+            match action {
+                HistoryAction::List { kind, limit } => print_json(&history.list(kind, limit)?),
+                HistoryAction::Show { id } => print_json(&history.get(id)?),
+                HistoryAction::Diff { from, to } => print_json(&history.compare(from, to)?),
+            }
+        }
+        Command::Dedupe { result, mode } => {
+            let result: DuplicateScanResult = read_json(&result)?;
+            print_json(&dedupe::dedupe_groups(&result.groups, mode))
+        }
+        Command::PruneEmpty { paths } => print_json(&empty_dirs::remove_empty_directories(&paths)),
+        Command::Plan { action } => match action {
+            PlanCommand::Create {
+                local,
+                score,
+                duplicates,
+                unused,
+                temporary,
+                destination,
+                output,
+            } => {
+                let mut options = PlanOptions {
+                    duplicates: None,
+                    unused,
+                    temporary,
+                    destination,
+                };
+                let plan = match (local, score) {
+                    (Some(local), _) => {
+                        options.duplicates = Some(duplicates.unwrap_or(PlanAction::Delete));
+                        CleanupPlan::from_folder_stats(&read_json(&local)?, &options)?
+                    }
+                    (None, Some(score)) => {
+                        CleanupPlan::from_burn_score(&read_json(&score)?, &options)?
+                    }
+                    (None, None) => return Err("Pass --local or --score".to_string()),
+                };
+
+                match output {
+                    Some(path) => {
+                        plan.save(&path)?;
+                        eprintln!(
+                            "✓ Wrote {} actions ({} bytes) to {}",
+                            plan.actions.len(),
+                            plan.total_bytes,
+                            path.display()
+                        );
+                        Ok(())
+                    }
+                    None => {
+                        println!("{}", plan.render(PlanFormat::Json)?);
+                        Ok(())
+                    }
+                }
+            }
+            PlanCommand::Apply {
+                plan: path,
+                dry_run,
+                quarantine: dir,
+                trash,
+            } => {
+                let cleanup_plan = CleanupPlan::load(&path)?;
+                let report = if trash {
+                    let trash = open_trash(None)?;
+                    plan::apply_plan(&cleanup_plan, Disposal::Trash(&trash), dry_run)?
+                } else {
+                    let quarantine = open_quarantine(dir)?;
+                    plan::apply_plan(&cleanup_plan, Disposal::Quarantine(&quarantine), dry_run)?
+                };
+                print_json(&report)
+            }
+        },
+        Command::Trash { log, action } => {
+            let trash = open_trash(log)?;
+
+            match action {
+                TrashAction::Add { paths } => print_json(&trash.trash(&paths)?),
+                TrashAction::Duplicates { result } => {
+                    let result: DuplicateScanResult = read_json(&result)?;
+                    print_json(&trash.trash_duplicates(&result.groups)?)
+                }
+                TrashAction::List => print_json(&trash.list()?),
+                TrashAction::Restore { trashed_paths } => {
+                    print_json(&trash.restore(&trashed_paths)?)
+                }
+            }
+        }
+        Command::Quarantine { dir, action } => {
+            let quarantine = open_quarantine(dir)?;
+
+            match action {
+                QuarantineAction::Add { paths, reason } => {
+                    print_json(&quarantine.quarantine(&paths, reason)?)
+                }
+                QuarantineAction::Duplicates { result } => {
+                    let result: DuplicateScanResult = read_json(&result)?;
+                    print_json(&quarantine.quarantine_duplicates(&result.groups)?)
+                }
+                QuarantineAction::List => print_json(&quarantine.list()?),
+                QuarantineAction::Restore { batch_id } => {
+                    print_json(&quarantine.restore(&batch_id)?)
+                }
+                QuarantineAction::Purge { older_than_days } => {
+                    print_json(&quarantine.purge(older_than_days)?)
+                }
+                QuarantineAction::Retention { days } => {
+                    print_json(&quarantine.set_retention_days(days)?)
+                }
+            }
+        }
+    }
+}
+
+fn open_history(cli: &Cli) -> Result<Option<ScanHistory>, String> {
+    if cli.no_history {
+        return Ok(None);
+    }
+
+    open_store(
+        cli.history.clone(),
+        history::HISTORY_FILE_NAME,
+        ScanHistory::open,
+    )
+    .map(Some)
+}
+
+/// Opens `path`, or `file_name` in the desktop app's data directory.
+fn open_store<T>(
+    path: Option<PathBuf>,
+    file_name: &str,
+    open: fn(&Path) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = path
+        .or_else(|| stackburn_lib::default_data_dir().map(|dir| dir.join(file_name)))
+        .ok_or_else(|| {
+            format!(
+                "No data directory for {}, pass its path explicitly",
+                file_name
+            )
+        })?;
+
+    open(&path)
+}
+
+fn open_quarantine(dir: Option<PathBuf>) -> Result<Quarantine, String> {
+    open_store(dir, quarantine::QUARANTINE_DIR_NAME, |dir| {
+        Quarantine::open(dir.to_path_buf())
+    })
+}
+
+fn open_trash(log: Option<PathBuf>) -> Result<Trash, String> {
+    open_store(log, trash::TRASH_LOG_FILE_NAME, |path| {
+        Ok(Trash::open(path.to_path_buf()))
+    })
+}
+
+/// A monitor cancelled by Ctrl-C, optionally printing progress to stderr
+fn scan_monitor(show_progress: bool) -> ScanMonitor {
+    let sink = show_progress.then(|| Arc::new(print_progress) as Arc<_>);
+    let monitor = ScanMonitor::new("cli", sink);
+
+    let on_interrupt = monitor.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("Stopping scan, press Ctrl-C again to quit");
+            on_interrupt.cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(130);
+            }
+        }
+    });
+
+    monitor
+}
+
+fn print_progress(progress: &ScanProgress) {
+    let eta = progress
+        .eta_seconds
+        .map(|s| format!(", ETA {:.0}s", s))
+        .unwrap_or_default();
+    eprintln!(
+        "[{:?}] {} files, {:.1}/{:.1} MB hashed, {} repos{} {}",
+        progress.phase,
+        progress.files_seen,
+        progress.bytes_hashed as f64 / 1_048_576.0,
+        progress.bytes_to_hash as f64 / 1_048_576.0,
+        progress.repos_fetched,
+        eta,
+        progress.current_path.as_deref().unwrap_or("")
+    );
+}
+
+fn check_complete(incomplete: bool) -> Result<(), String> {
+    if incomplete {
+        Err("Scan cancelled, the result above is incomplete".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn warn_on_error(result: Result<i64, String>) {
+    if let Err(e) = result {
+        eprintln!("Failed to record scan history: {}", e);
+    }
+}
+
+/// Reads and parses a JSON file, or stdin when `path` is "-".
+fn read_json<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let contents = if path == "-" {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        buffer
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?
+    };
+
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize result: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
@@ -1,14 +1,97 @@
 // src-tauri/src/burn_score.rs
+#[cfg(feature = "desktop")]
+use crate::history::ScanHistory;
+use crate::scanners::build_artifacts::ArtifactScanResult;
+use crate::scanners::drive_scanner::DriveScanResult;
+use crate::scanners::file_types;
+use crate::scanners::github_scanner::GitHubScanResult;
+use crate::scanners::local_scanner::FolderStats;
+use crate::scanners::temp_files::TempClassifier;
+use crate::scanners::versions::VersionCluster;
+use crate::scanners::{ItemSource, ScannedItem};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tauri::command;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(try_from = "RawBurnScoreInput")]
 pub struct BurnScoreInput {
-    pub drive_data: Option<serde_json::Value>,
-    pub local_data: Option<serde_json::Value>,
-    pub github_data: Option<serde_json::Value>,
+    pub drive_data: Option<DriveScanResult>,
+    pub local_data: Option<FolderStats>,
+    pub github_data: Option<GitHubScanResult>,
+    pub artifact_data: Option<ArtifactScanResult>,
+    // Normalized items from any `Scanner`, scored per source
+    pub items: Vec<ScannedItem>,
+}
+
+// Untyped form of `BurnScoreInput`, so parse errors can name the section they came from
+#[derive(Deserialize)]
+struct RawBurnScoreInput {
+    drive_data: Option<serde_json::Value>,
+    local_data: Option<serde_json::Value>,
+    github_data: Option<serde_json::Value>,
+    #[serde(default)]
+    artifact_data: Option<serde_json::Value>,
+    #[serde(default)]
+    items: Option<serde_json::Value>,
+}
+
+impl TryFrom<RawBurnScoreInput> for BurnScoreInput {
+    type Error = String;
+
+    fn try_from(raw: RawBurnScoreInput) -> Result<Self, Self::Error> {
+        fn parse<T: serde::de::DeserializeOwned>(
+            section: &str,
+            value: Option<serde_json::Value>,
+        ) -> Result<Option<T>, String> {
+            match value {
+                None | Some(serde_json::Value::Null) => Ok(None),
+                Some(value) => serde_json::from_value(value)
+                    .map(Some)
+                    .map_err(|e| format!("Invalid {}: {}", section, e)),
+            }
+        }
+
+        let input = BurnScoreInput {
+            drive_data: parse("drive_data", raw.drive_data)?,
+            local_data: parse("local_data", raw.local_data)?,
+            github_data: parse("github_data", raw.github_data)?,
+            artifact_data: parse("artifact_data", raw.artifact_data)?,
+            items: parse("items", raw.items)?.unwrap_or_default(),
+        };
+        input.validate()?;
+        Ok(input)
+    }
+}
+
+impl BurnScoreInput {
+    /// Checks that every section is internally consistent, so malformed
+    /// input is rejected instead of quietly producing a low score.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        if let Some(local) = &self.local_data {
+            validate_local_data(local, &mut problems);
+        }
+        if let Some(github) = &self.github_data {
+            validate_github_data(github, &mut problems);
+        }
+        if let Some(drive) = &self.drive_data {
+            validate_drive_data(drive, &mut problems);
+        }
+        if let Some(artifacts) = &self.artifact_data {
+            validate_artifact_data(artifacts, &mut problems);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid burn score input: {}", problems.join("; ")))
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +102,10 @@ pub struct BurnScoreResult {
     pub total_files_scanned: usize,
     pub recommendations: Vec<Recommendation>,
     pub file_categories: FileCategories,
+    #[serde(default)]
+    pub file_types: HashMap<String, HashMap<String, usize>>, // Files per category (Images, Videos, ...) for each source
+    #[serde(default)]
+    pub coverage_percent: Option<f64>, // Share of local paths the scan could read; None without a local scan
     pub potential_savings_gb: f64,
     pub calculated_at: DateTime<Utc>,
 }
@@ -31,6 +118,14 @@ pub struct FileCategories {
     pub archived: CategoryStats,
     pub large_unused: CategoryStats,
     pub temporary: CategoryStats,
+    #[serde(default)]
+    pub build_artifacts: CategoryStats, // Dependency and build output a project can regenerate
+    #[serde(default)]
+    pub near_duplicates: CategoryStats, // Look-alike copies, e.g. resized photos; need a review
+    #[serde(default)]
+    pub unpacked_archives: CategoryStats, // Archives already extracted, and repeated extractions
+    #[serde(default)]
+    pub structural_junk: CategoryStats, // Empty folders, zero-byte files and broken symlinks; no size
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +134,27 @@ pub struct CategoryStats {
     pub total_size_gb: f64,
     pub percentage_of_total: f64,
     pub items: Vec<String>, // Sample file paths or names
+    #[serde(default)]
+    pub local_paths: Vec<String>, // Every file on this machine counted here, which cleanup plans may act on
+    #[serde(default)]
+    pub regenerable: bool, // Everything counted here can be rebuilt, so deleting it is safe
+}
+
+impl CategoryStats {
+    /// Lists a file from a local scan as a sample
+    fn push_local(&mut self, path: &str) {
+        self.items.push(path.to_string());
+        self.local_paths.push(path.to_string());
+    }
+
+    /// Records a file from a local scan, listing it as a sample only while
+    /// there are fewer than `MAX_SAMPLE_ITEMS`
+    fn push_local_capped(&mut self, path: &str) {
+        if self.items.len() < MAX_SAMPLE_ITEMS {
+            self.items.push(path.to_string());
+        }
+        self.local_paths.push(path.to_string());
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -68,63 +184,105 @@ pub enum EffortLevel {
 
 const STALE_DAYS: i64 = 180; // 6 months
 const LARGE_FILE_THRESHOLD_GB: f64 = 0.1; // 100MB
+const MAX_SAMPLE_ITEMS: usize = 10;
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn calculate_burn_score(input: BurnScoreInput) -> Result<BurnScoreResult, String> {
+pub async fn calculate_burn_score(
+    input: BurnScoreInput,
+    history: State<'_, ScanHistory>,
+) -> Result<BurnScoreResult, String> {
+    let result = compute_burn_score(input)?;
+
+    if let Err(e) = history.record_burn_score(&result) {
+        eprintln!("Failed to record scan history: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// Scores the combined scan results; higher means more bloat.
+pub fn compute_burn_score(input: BurnScoreInput) -> Result<BurnScoreResult, String> {
+    input.validate()?;
+
     let mut total_files = 0;
     let mut total_size_bytes = 0u64;
     let mut category_scores = HashMap::new();
-    let mut file_categories = FileCategories {
-        duplicates: CategoryStats::default(),
-        versioned: CategoryStats::default(),
-        stale: CategoryStats::default(),
-        archived: CategoryStats::default(),
-        large_unused: CategoryStats::default(),
-        temporary: CategoryStats::default(),
-    };
+    let mut file_categories = FileCategories::default();
+    let mut file_types = HashMap::new();
+    let mut coverage_percent = None;
 
     // Process Google Drive data
     if let Some(drive_data) = &input.drive_data {
-        let (drive_score, drive_stats) = analyze_drive_data(drive_data)?;
-        category_scores.insert("Google Drive".to_string(), drive_score);
+        let (drive_score, drive_stats) = analyze_drive_data(drive_data);
+        category_scores.insert(ItemSource::Drive.label().to_string(), drive_score);
         merge_category_stats(&mut file_categories, drive_stats);
+        file_types.insert(
+            ItemSource::Drive.label().to_string(),
+            drive_data.file_types.clone(),
+        );
 
-        if let Some(count) = drive_data["total_files"].as_u64() {
-            total_files += count as usize;
-        }
-        if let Some(size) = drive_data["total_size"].as_u64() {
-            total_size_bytes += size;
-        }
+        total_files += drive_data.total_files;
+        total_size_bytes += drive_data.total_size as u64;
     }
 
     // Process Local Files data
     if let Some(local_data) = &input.local_data {
-        let (local_score, local_stats) = analyze_local_data(local_data)?;
-        category_scores.insert("Local Files".to_string(), local_score);
+        let (local_score, local_stats) = analyze_local_data(local_data);
+        category_scores.insert(ItemSource::Local.label().to_string(), local_score);
         merge_category_stats(&mut file_categories, local_stats);
-
-        if let Some(count) = local_data["total_files"].as_u64() {
-            total_files += count as usize;
-        }
-        if let Some(size) = local_data["total_size"].as_u64() {
-            total_size_bytes += size;
-        }
+        file_types.insert(
+            ItemSource::Local.label().to_string(),
+            local_data.file_types.clone(),
+        );
+        coverage_percent = Some(local_coverage(local_data));
+
+        total_files += local_data.total_files;
+        total_size_bytes += local_data.total_size;
     }
 
     // Process GitHub data
     if let Some(github_data) = &input.github_data {
-        let (github_score, github_stats) = analyze_github_data(github_data)?;
-        category_scores.insert("GitHub".to_string(), github_score);
+        let (github_score, github_stats) = analyze_github_data(github_data);
+        category_scores.insert(ItemSource::GitHub.label().to_string(), github_score);
         merge_category_stats(&mut file_categories, github_stats);
 
-        if let Some(count) = github_data["total_repos"].as_u64() {
-            total_files += count as usize; // Count repos as "files"
-        }
-        if let Some(size_kb) = github_data["total_size_kb"].as_u64() {
-            total_size_bytes += size_kb * 1024;
-        }
+        total_files += github_data.total_repos; // Count repos as "files"
+        total_size_bytes += github_data.total_size_kb as u64 * 1024;
+    }
+
+    // Process build artifacts, which local scans skip by default
+    if let Some(artifact_data) = &input.artifact_data {
+        let (artifact_score, artifact_stats) = analyze_artifact_data(artifact_data);
+        let score = category_scores
+            .entry(ItemSource::Local.label().to_string())
+            .or_insert(0.0);
+        *score = (*score + artifact_score).min(100.0);
+        merge_category_stats(&mut file_categories, artifact_stats);
+
+        total_files += artifact_data
+            .artifacts
+            .iter()
+            .map(|a| a.file_count)
+            .sum::<usize>();
+        total_size_bytes += artifact_data.total_size_bytes;
+    }
+
+    // Process normalized items
+    for (source, items) in group_items_by_source(&input.items) {
+        let (items_score, items_stats) = analyze_items(&items);
+        let score = category_scores
+            .entry(source.label().to_string())
+            .or_insert(0.0);
+        *score = (*score + items_score).min(100.0);
+        merge_category_stats(&mut file_categories, items_stats);
+
+        total_files += items.len();
+        total_size_bytes += items.iter().map(|i| i.size_bytes).sum::<u64>();
     }
 
+    set_percentages(&mut file_categories, total_size_bytes);
+
     // Calculate overall burn score
     let overall_score = calculate_overall_score(&category_scores, &file_categories);
 
@@ -133,7 +291,8 @@ pub async fn calculate_burn_score(input: BurnScoreInput) -> Result<BurnScoreResu
     let potential_savings_gb = calculate_potential_savings(&file_categories);
 
     // Generate recommendations
-    let recommendations = generate_recommendations(&file_categories, &category_scores);
+    let recommendations =
+        generate_recommendations(&file_categories, &category_scores, coverage_percent);
 
     Ok(BurnScoreResult {
         overall_score,
@@ -142,18 +301,20 @@ pub async fn calculate_burn_score(input: BurnScoreInput) -> Result<BurnScoreResu
         total_files_scanned: total_files,
         recommendations,
         file_categories,
+        file_types,
+        coverage_percent,
         potential_savings_gb,
         calculated_at: Utc::now(),
     })
 }
 
-#[command]
+#[cfg_attr(feature = "desktop", command)]
 pub async fn get_file_categories(input: BurnScoreInput) -> Result<FileCategories, String> {
-    let result = calculate_burn_score(input).await?;
+    let result = compute_burn_score(input)?;
     Ok(result.file_categories)
 }
 
-#[command]
+#[cfg_attr(feature = "desktop", command)]
 pub async fn generate_report(burn_score: BurnScoreResult) -> Result<String, String> {
     let mut report = String::new();
 
@@ -179,9 +340,13 @@ pub async fn generate_report(burn_score: BurnScoreResult) -> Result<String, Stri
         burn_score.total_bloat_size_gb
     ));
     report.push_str(&format!(
-        "- Potential savings: {:.2} GB\n\n",
+        "- Potential savings: {:.2} GB\n",
         burn_score.potential_savings_gb
     ));
+    if let Some(coverage) = burn_score.coverage_percent {
+        report.push_str(&format!("- Local scan coverage: {:.1}%\n", coverage));
+    }
+    report.push('\n');
 
     report.push_str(&format!("## Category Breakdown\n"));
     for (category, score) in &burn_score.category_scores {
@@ -210,6 +375,16 @@ pub async fn generate_report(burn_score: BurnScoreResult) -> Result<String, Stri
         burn_score.file_categories.stale.total_size_gb
     ));
 
+    report.push_str("### Temporary Files\n");
+    report.push_str(&format!(
+        "- Count: {}\n",
+        burn_score.file_categories.temporary.count
+    ));
+    report.push_str(&format!(
+        "- Size: {:.2} GB\n\n",
+        burn_score.file_categories.temporary.total_size_gb
+    ));
+
     report.push_str(&format!("## Top Recommendations\n"));
     for (i, rec) in burn_score.recommendations.iter().take(5).enumerate() {
         report.push_str(&format!(
@@ -227,179 +402,499 @@ pub async fn generate_report(burn_score: BurnScoreResult) -> Result<String, Stri
     Ok(report)
 }
 
-fn analyze_drive_data(data: &serde_json::Value) -> Result<(f64, FileCategories), String> {
-    let mut categories = FileCategories::default();
-    let mut score = 0.0;
-
-    // Analyze file types distribution
-    if let Some(file_types) = data["file_types"].as_object() {
-        let total_files = data["total_files"].as_u64().unwrap_or(0) as f64;
+fn validate_local_data(data: &FolderStats, problems: &mut Vec<String>) {
+    if data.largest_files.len() > data.total_files || data.unused_files.len() > data.total_files {
+        problems.push(format!(
+            "local_data lists more files than total_files ({})",
+            data.total_files
+        ));
+    }
+    if data.file_types.values().sum::<usize>() > data.total_files {
+        problems.push("local_data file_types count more files than total_files".to_string());
+    }
+    if data.errors.len() > data.error_counts.total() {
+        problems.push("local_data lists more errors than error_counts add up to".to_string());
+    }
+    for group in &data.duplicates {
+        if group.files.len() < 2 {
+            problems.push(format!(
+                "local_data duplicate group {} has fewer than 2 files",
+                group.hash
+            ));
+        }
+    }
+    for (i, group) in data.near_duplicates.iter().enumerate() {
+        if group.files.len() < 2 {
+            problems.push(format!(
+                "local_data near-duplicate group {} has fewer than 2 files",
+                i
+            ));
+        }
+    }
+}
 
-        // Check for high percentage of certain file types
-        for (file_type, count) in file_types {
-            let percentage = (count.as_u64().unwrap_or(0) as f64 / total_files) * 100.0;
+fn validate_github_data(data: &GitHubScanResult, problems: &mut Vec<String>) {
+    if data.private_repos + data.public_repos != data.total_repos {
+        problems.push(format!(
+            "github_data private_repos ({}) + public_repos ({}) != total_repos ({})",
+            data.private_repos, data.public_repos, data.total_repos
+        ));
+    }
+    if data.total_size_kb < 0 {
+        problems.push("github_data total_size_kb is negative".to_string());
+    }
+    for repo in data
+        .stale_repos
+        .iter()
+        .chain(&data.archived_repos)
+        .chain(&data.inactive_forks)
+    {
+        if repo.size < 0 {
+            problems.push(format!(
+                "github_data repo {} has a negative size",
+                repo.full_name
+            ));
+        }
+    }
+}
 
-            match file_type.as_str() {
-                "Videos" | "Images" if percentage > 40.0 => score += 10.0,
-                "Other" if percentage > 30.0 => score += 5.0,
-                _ => {}
-            }
+fn validate_drive_data(data: &DriveScanResult, problems: &mut Vec<String>) {
+    if data.total_size < 0 {
+        problems.push("drive_data total_size is negative".to_string());
+    }
+    if data.file_types.values().sum::<usize>() > data.total_files {
+        problems.push("drive_data file_types count more files than total_files".to_string());
+    }
+    for file in &data.oldest_files {
+        if DateTime::parse_from_rfc3339(&file.modified_time).is_err() {
+            problems.push(format!(
+                "drive_data file {} has an invalid modified_time {:?}",
+                file.name, file.modified_time
+            ));
         }
     }
+}
+
+fn validate_artifact_data(data: &ArtifactScanResult, problems: &mut Vec<String>) {
+    let listed: u64 = data.artifacts.iter().map(|a| a.size_bytes).sum();
+    if listed != data.total_size_bytes {
+        problems.push(format!(
+            "artifact_data artifacts add up to {} bytes, not total_size_bytes ({})",
+            listed, data.total_size_bytes
+        ));
+    }
+    if data.regenerable_size_bytes > data.total_size_bytes {
+        problems.push("artifact_data regenerable_size_bytes exceeds total_size_bytes".to_string());
+    }
+}
+
+fn analyze_drive_data(data: &DriveScanResult) -> (f64, FileCategories) {
+    let mut categories = FileCategories::default();
+    let mut score = file_type_score(&data.file_types, data.total_files);
 
     // Analyze oldest files
-    if let Some(oldest_files) = data["oldest_files"].as_array() {
-        let cutoff = Utc::now() - Duration::days(STALE_DAYS);
-
-        for file in oldest_files {
-            if let Some(modified) = file["modified_time"].as_str() {
-                if let Ok(date) = DateTime::parse_from_rfc3339(modified) {
-                    if date.with_timezone(&Utc) < cutoff {
-                        categories.stale.count += 1;
-                        if let Some(size) = file["size"].as_u64() {
-                            categories.stale.total_size_gb += size as f64 / 1_073_741_824.0;
-                        }
-                        if let Some(name) = file["name"].as_str() {
-                            categories.stale.items.push(name.to_string());
-                        }
-                    }
+    let cutoff = Utc::now() - Duration::days(STALE_DAYS);
+
+    for file in &data.oldest_files {
+        if let Ok(date) = DateTime::parse_from_rfc3339(&file.modified_time) {
+            if date.with_timezone(&Utc) < cutoff {
+                categories.stale.count += 1;
+                if let Some(size) = file.size {
+                    categories.stale.total_size_gb += size as f64 / 1_073_741_824.0;
                 }
+                categories.stale.items.push(file.name.clone());
             }
         }
+    }
+
+    score += (categories.stale.count as f64).min(20.0);
+
+    add_version_clusters(&mut categories, &data.versioned);
+    score += (categories.versioned.count as f64).min(15.0);
 
-        score += (categories.stale.count as f64).min(20.0);
+    (score.min(100.0), categories)
+}
+
+/// Points for a breakdown dominated by media or unrecognized files. Drive and
+/// local scans both key `file_types` by category, so they score alike.
+fn file_type_score(file_types: &HashMap<String, usize>, total_files: usize) -> f64 {
+    let mut score = 0.0;
+    if total_files == 0 {
+        return score;
     }
+    let total_files = total_files as f64;
 
-    Ok((score.min(100.0), categories))
+    // Check for high percentage of certain file types
+    for (file_type, count) in file_types {
+        let percentage = (*count as f64 / total_files) * 100.0;
+
+        match file_type.as_str() {
+            file_types::VIDEOS | file_types::IMAGES if percentage > 40.0 => score += 10.0,
+            file_types::OTHER if percentage > 30.0 => score += 5.0,
+            _ => {}
+        }
+    }
+    score
+}
+
+/// Share of the files and folders a local scan came across that it could read
+fn local_coverage(data: &FolderStats) -> f64 {
+    let reached = data.total_files + data.total_directories;
+    let unread = data.error_counts.total();
+    if reached + unread == 0 {
+        return 100.0;
+    }
+    reached as f64 / (reached + unread) as f64 * 100.0
 }
 
-fn analyze_local_data(data: &serde_json::Value) -> Result<(f64, FileCategories), String> {
+fn analyze_local_data(data: &FolderStats) -> (f64, FileCategories) {
     let mut categories = FileCategories::default();
-    let mut score = 0.0;
+    let mut score = file_type_score(&data.file_types, data.total_files);
 
     // Analyze duplicates
-    if let Some(duplicates) = data["duplicates"].as_array() {
-        for dup_group in duplicates {
-            if let Some(files) = dup_group["files"].as_array() {
-                categories.duplicates.count += files.len() - 1; // Subtract original
-                if let Some(size) = dup_group["total_size"].as_u64() {
-                    categories.duplicates.total_size_gb += size as f64 / 1_073_741_824.0;
-                }
+    for dup_group in &data.duplicates {
+        categories.duplicates.count += dup_group.files.len() - 1; // Subtract original
+        categories.duplicates.total_size_gb += dup_group.total_size as f64 / 1_073_741_824.0;
 
-                // Add sample files
-                for file in files.iter().skip(1).take(3) {
-                    if let Some(path) = file["path"].as_str() {
-                        categories.duplicates.items.push(path.to_string());
-                    }
-                }
-            }
+        // Add sample files
+        for file in dup_group.files.iter().skip(1).take(3) {
+            categories.duplicates.push_local(&file.path);
         }
-
-        score += (categories.duplicates.count as f64 * 2.0).min(30.0);
     }
 
+    score += (categories.duplicates.count as f64 * 2.0).min(30.0);
+
     // Analyze large files
-    if let Some(largest_files) = data["largest_files"].as_array() {
-        for file in largest_files {
-            if let Some(size) = file["size"].as_u64() {
-                let size_gb = size as f64 / 1_073_741_824.0;
-                if size_gb > LARGE_FILE_THRESHOLD_GB {
-                    categories.large_unused.count += 1;
-                    categories.large_unused.total_size_gb += size_gb;
-                    if let Some(name) = file["name"].as_str() {
-                        categories.large_unused.items.push(name.to_string());
-                    }
-                }
-            }
+    for file in &data.largest_files {
+        let size_gb = file.size as f64 / 1_073_741_824.0;
+        if size_gb > LARGE_FILE_THRESHOLD_GB {
+            categories.large_unused.count += 1;
+            categories.large_unused.total_size_gb += size_gb;
+            categories.large_unused.items.push(file.name.clone());
         }
-
-        score += (categories.large_unused.count as f64).min(20.0);
     }
 
+    score += (categories.large_unused.count as f64).min(20.0);
+
     // Analyze unused files
-    if let Some(unused_files) = data["unused_files"].as_array() {
-        categories.stale.count += unused_files.len();
-        for file in unused_files {
-            if let Some(size) = file["size"].as_u64() {
-                categories.stale.total_size_gb += size as f64 / 1_073_741_824.0;
+    categories.stale.count += data.unused_files.len();
+    for file in &data.unused_files {
+        categories.stale.total_size_gb += file.size as f64 / 1_073_741_824.0;
+        categories.stale.push_local_capped(&file.path);
+    }
+
+    score += (data.unused_files.len() as f64).min(20.0);
+
+    // Analyze temp and cache files
+    let temporary = &data.temporary;
+    if temporary.count > 0 {
+        categories.temporary.count += temporary.count;
+        categories.temporary.total_size_gb += temporary.total_size as f64 / 1_073_741_824.0;
+        for file in &temporary.largest {
+            categories.temporary.push_local_capped(&file.path);
+        }
+        categories.temporary.regenerable = true;
+    }
+
+    score += (categories.temporary.total_size_gb * 5.0).min(15.0);
+
+    // Analyze near-duplicates
+    for group in &data.near_duplicates {
+        categories.near_duplicates.count += group.files.len().saturating_sub(1); // Subtract the kept copy
+        categories.near_duplicates.total_size_gb += group.total_size as f64 / 1_073_741_824.0;
+        for file in group.files.iter().skip(1).take(3) {
+            categories.near_duplicates.push_local(&file.path);
+        }
+    }
+
+    score += (categories.near_duplicates.count as f64).min(15.0);
+
+    // Analyze archives whose contents also exist unpacked. Two archives of the
+    // same files both list the spare extraction, so count each path once.
+    let mut counted = HashSet::new();
+    for archive in &data.archives {
+        for path in &archive.redundant {
+            if !counted.insert(path.as_str()) {
+                continue;
             }
+            let size = if *path == archive.path {
+                archive.size
+            } else {
+                archive
+                    .extractions
+                    .iter()
+                    .find(|e| e.folder == *path)
+                    .map_or(0, |e| e.matched_size)
+            };
+            categories.unpacked_archives.count += 1;
+            categories.unpacked_archives.total_size_gb += size as f64 / 1_073_741_824.0;
+            categories.unpacked_archives.push_local(path);
         }
+    }
 
-        score += (unused_files.len() as f64).min(20.0);
+    score += (categories.unpacked_archives.total_size_gb * 5.0).min(15.0);
+
+    // Analyze empty folders, zero-byte files and broken links
+    let junk = &data.structural_junk;
+    categories.structural_junk.count += junk.count();
+    for path in junk
+        .empty_directories
+        .iter()
+        .chain(&junk.zero_byte_files)
+        .chain(junk.broken_symlinks.iter().map(|link| &link.path))
+        .take(10)
+    {
+        categories.structural_junk.push_local(path);
     }
 
-    Ok((score.min(100.0), categories))
+    // Clutter rather than waste, so it weighs lightly
+    score += (junk.count() as f64 / 10.0).min(10.0);
+
+    add_version_clusters(&mut categories, &data.versioned);
+    score += (categories.versioned.count as f64).min(15.0);
+
+    (score.min(100.0), categories)
 }
 
-fn analyze_github_data(data: &serde_json::Value) -> Result<(f64, FileCategories), String> {
+/// Counts every version but the newest, listing each cluster as
+/// "kept ← older, older"
+fn add_version_clusters(categories: &mut FileCategories, clusters: &[VersionCluster]) {
+    for cluster in clusters {
+        categories.versioned.count += cluster.older.len();
+        categories.versioned.total_size_gb += cluster.wasted_size as f64 / 1_073_741_824.0;
+
+        if categories.versioned.items.len() < 10 {
+            let older: Vec<&str> = cluster.older.iter().map(|f| f.name.as_str()).collect();
+            categories.versioned.items.push(format!(
+                "{} ← {}",
+                cluster.canonical.path,
+                older.join(", ")
+            ));
+        }
+    }
+}
+
+fn analyze_github_data(data: &GitHubScanResult) -> (f64, FileCategories) {
     let mut categories = FileCategories::default();
     let mut score = 0.0;
 
     // Analyze stale repos
-    if let Some(stale_repos) = data["stale_repos"].as_array() {
-        categories.stale.count += stale_repos.len();
-        for repo in stale_repos {
-            if let Some(size) = repo["size"].as_u64() {
-                categories.stale.total_size_gb += (size * 1024) as f64 / 1_073_741_824.0;
-            }
-            if let Some(name) = repo["full_name"].as_str() {
-                categories.stale.items.push(name.to_string());
-            }
-        }
-
-        score += (stale_repos.len() as f64 * 3.0).min(30.0);
+    categories.stale.count += data.stale_repos.len();
+    for repo in &data.stale_repos {
+        categories.stale.total_size_gb += (repo.size * 1024) as f64 / 1_073_741_824.0;
+        categories.stale.items.push(repo.full_name.clone());
     }
 
+    score += (data.stale_repos.len() as f64 * 3.0).min(30.0);
+
     // Analyze archived repos
-    if let Some(archived_repos) = data["archived_repos"].as_array() {
-        categories.archived.count += archived_repos.len();
-        for repo in archived_repos {
-            if let Some(size) = repo["size"].as_u64() {
-                categories.archived.total_size_gb += (size * 1024) as f64 / 1_073_741_824.0;
-            }
-            if let Some(name) = repo["full_name"].as_str() {
-                categories.archived.items.push(name.to_string());
-            }
+    categories.archived.count += data.archived_repos.len();
+    for repo in &data.archived_repos {
+        categories.archived.total_size_gb += (repo.size * 1024) as f64 / 1_073_741_824.0;
+        categories.archived.items.push(repo.full_name.clone());
+    }
+
+    score += (data.archived_repos.len() as f64 * 2.0).min(20.0);
+
+    // Analyze inactive forks
+    categories.versioned.count += data.inactive_forks.len();
+    for fork in &data.inactive_forks {
+        categories.versioned.total_size_gb += (fork.size * 1024) as f64 / 1_073_741_824.0;
+    }
+
+    score += (data.inactive_forks.len() as f64 * 2.0).min(20.0);
+
+    (score.min(100.0), categories)
+}
+
+fn analyze_artifact_data(data: &ArtifactScanResult) -> (f64, FileCategories) {
+    let mut categories = FileCategories::default();
+    let cutoff_date = Utc::now() - Duration::days(STALE_DAYS);
+    let mut idle_projects = 0;
+
+    // Only artifacts with an owning project are known to be safe to delete
+    for artifact in data.artifacts.iter().filter(|a| a.regenerable) {
+        categories.build_artifacts.count += 1;
+        categories.build_artifacts.total_size_gb += artifact.size_bytes as f64 / 1_073_741_824.0;
+        categories.build_artifacts.push_local_capped(&artifact.path);
+        if artifact
+            .project_last_modified
+            .is_some_and(|t| t < cutoff_date)
+        {
+            idle_projects += 1;
         }
+    }
+    categories.build_artifacts.regenerable = true;
+
+    let score = (categories.build_artifacts.total_size_gb * 2.0).min(30.0)
+        + (idle_projects as f64 * 2.0).min(10.0);
 
-        score += (archived_repos.len() as f64 * 2.0).min(20.0);
+    (score.min(100.0), categories)
+}
+
+fn group_items_by_source(items: &[ScannedItem]) -> HashMap<ItemSource, Vec<&ScannedItem>> {
+    let mut grouped: HashMap<ItemSource, Vec<&ScannedItem>> = HashMap::new();
+    for item in items {
+        grouped.entry(item.source).or_default().push(item);
     }
+    grouped
+}
 
-    // Analyze inactive forks
-    if let Some(inactive_forks) = data["inactive_forks"].as_array() {
-        categories.versioned.count += inactive_forks.len();
-        for fork in inactive_forks {
-            if let Some(size) = fork["size"].as_u64() {
-                categories.versioned.total_size_gb += (size * 1024) as f64 / 1_073_741_824.0;
+fn analyze_items(items: &[&ScannedItem]) -> (f64, FileCategories) {
+    let mut categories = FileCategories::default();
+    let mut score = 0.0;
+
+    // Analyze duplicates by content fingerprint
+    let mut by_fingerprint: HashMap<&str, Vec<&ScannedItem>> = HashMap::new();
+    for item in items {
+        if let Some(fingerprint) = &item.fingerprint {
+            by_fingerprint.entry(fingerprint).or_default().push(item);
+        }
+    }
+    for group in by_fingerprint.values().filter(|g| g.len() > 1) {
+        categories.duplicates.count += group.len() - 1; // Subtract original
+        categories.duplicates.total_size_gb +=
+            (group[0].size_bytes * (group.len() - 1) as u64) as f64 / 1_073_741_824.0;
+        for item in group.iter().skip(1).take(3) {
+            if item.source == ItemSource::Local {
+                categories.duplicates.push_local(&item.location);
+            } else {
+                categories.duplicates.items.push(item.location.clone());
             }
         }
+    }
+    score += (categories.duplicates.count as f64 * 2.0).min(30.0);
+
+    // Analyze stale and large items
+    let cutoff = Utc::now() - Duration::days(STALE_DAYS);
+    for item in items {
+        let size_gb = item.size_bytes as f64 / 1_073_741_824.0;
+
+        if size_gb > LARGE_FILE_THRESHOLD_GB {
+            categories.large_unused.count += 1;
+            categories.large_unused.total_size_gb += size_gb;
+            categories.large_unused.items.push(item.name.clone());
+        }
 
-        score += (inactive_forks.len() as f64 * 2.0).min(20.0);
+        let last_used = item.accessed_at.or(item.modified_at);
+        if last_used.map(|t| t < cutoff).unwrap_or(false) {
+            categories.stale.count += 1;
+            categories.stale.total_size_gb += size_gb;
+            if item.source == ItemSource::Local {
+                categories.stale.push_local(&item.location);
+            } else {
+                categories.stale.items.push(item.location.clone());
+            }
+        }
+    }
+    score += (categories.large_unused.count as f64).min(20.0);
+    score += (categories.stale.count as f64).min(20.0);
+
+    // Analyze temp and cache files on local disks
+    let classifier = TempClassifier::new();
+    for item in items.iter().filter(|i| i.source == ItemSource::Local) {
+        if classifier.classify(Path::new(&item.location)).is_some() {
+            categories.temporary.count += 1;
+            categories.temporary.total_size_gb += item.size_bytes as f64 / 1_073_741_824.0;
+            categories.temporary.push_local_capped(&item.location);
+            categories.temporary.regenerable = true;
+        }
     }
+    score += (categories.temporary.total_size_gb * 5.0).min(15.0);
 
-    Ok((score.min(100.0), categories))
+    (score.min(100.0), categories)
 }
 
 fn merge_category_stats(target: &mut FileCategories, source: FileCategories) {
     target.duplicates.count += source.duplicates.count;
     target.duplicates.total_size_gb += source.duplicates.total_size_gb;
     target.duplicates.items.extend(source.duplicates.items);
+    target
+        .duplicates
+        .local_paths
+        .extend(source.duplicates.local_paths);
 
     target.versioned.count += source.versioned.count;
     target.versioned.total_size_gb += source.versioned.total_size_gb;
     target.versioned.items.extend(source.versioned.items);
+    target
+        .versioned
+        .local_paths
+        .extend(source.versioned.local_paths);
 
     target.stale.count += source.stale.count;
     target.stale.total_size_gb += source.stale.total_size_gb;
     target.stale.items.extend(source.stale.items);
+    target.stale.local_paths.extend(source.stale.local_paths);
 
     target.archived.count += source.archived.count;
     target.archived.total_size_gb += source.archived.total_size_gb;
     target.archived.items.extend(source.archived.items);
+    target
+        .archived
+        .local_paths
+        .extend(source.archived.local_paths);
 
     target.large_unused.count += source.large_unused.count;
     target.large_unused.total_size_gb += source.large_unused.total_size_gb;
     target.large_unused.items.extend(source.large_unused.items);
+    target
+        .large_unused
+        .local_paths
+        .extend(source.large_unused.local_paths);
+
+    target.build_artifacts.count += source.build_artifacts.count;
+    target.build_artifacts.total_size_gb += source.build_artifacts.total_size_gb;
+    target
+        .build_artifacts
+        .items
+        .extend(source.build_artifacts.items);
+    target
+        .build_artifacts
+        .local_paths
+        .extend(source.build_artifacts.local_paths);
+    target.build_artifacts.regenerable |= source.build_artifacts.regenerable;
+
+    target.temporary.count += source.temporary.count;
+    target.temporary.total_size_gb += source.temporary.total_size_gb;
+    target.temporary.items.extend(source.temporary.items);
+    target
+        .temporary
+        .local_paths
+        .extend(source.temporary.local_paths);
+    target.temporary.regenerable |= source.temporary.regenerable;
+
+    target.near_duplicates.count += source.near_duplicates.count;
+    target.near_duplicates.total_size_gb += source.near_duplicates.total_size_gb;
+    target
+        .near_duplicates
+        .items
+        .extend(source.near_duplicates.items);
+    target
+        .near_duplicates
+        .local_paths
+        .extend(source.near_duplicates.local_paths);
+
+    target.unpacked_archives.count += source.unpacked_archives.count;
+    target.unpacked_archives.total_size_gb += source.unpacked_archives.total_size_gb;
+    target
+        .unpacked_archives
+        .items
+        .extend(source.unpacked_archives.items);
+    target
+        .unpacked_archives
+        .local_paths
+        .extend(source.unpacked_archives.local_paths);
+
+    target.structural_junk.count += source.structural_junk.count;
+    target
+        .structural_junk
+        .items
+        .extend(source.structural_junk.items);
+    target
+        .structural_junk
+        .local_paths
+        .extend(source.structural_junk.local_paths);
 }
 
 fn calculate_overall_score(
@@ -411,12 +906,9 @@ fn calculate_overall_score(
     let mut weight_sum = 0.0;
 
     for (category, score) in category_scores {
-        let weight = match category.as_str() {
-            "Local Files" => 0.4,
-            "Google Drive" => 0.35,
-            "GitHub" => 0.25,
-            _ => 0.0,
-        };
+        let weight = ItemSource::from_label(category)
+            .map(|source| source.score_weight())
+            .unwrap_or(0.0);
 
         total_score += score * weight;
         weight_sum += weight;
@@ -429,6 +921,28 @@ fn calculate_overall_score(
     }
 }
 
+/// Sets each category's share of the size of everything scanned
+fn set_percentages(categories: &mut FileCategories, total_size_bytes: u64) {
+    if total_size_bytes == 0 {
+        return;
+    }
+    let total_gb = total_size_bytes as f64 / 1_073_741_824.0;
+    for stats in [
+        &mut categories.duplicates,
+        &mut categories.versioned,
+        &mut categories.stale,
+        &mut categories.archived,
+        &mut categories.large_unused,
+        &mut categories.temporary,
+        &mut categories.build_artifacts,
+        &mut categories.near_duplicates,
+        &mut categories.unpacked_archives,
+        &mut categories.structural_junk,
+    ] {
+        stats.percentage_of_total = (stats.total_size_gb / total_gb * 100.0).min(100.0);
+    }
+}
+
 fn calculate_total_bloat(categories: &FileCategories) -> f64 {
     categories.duplicates.total_size_gb
         + categories.stale.total_size_gb
@@ -436,6 +950,9 @@ fn calculate_total_bloat(categories: &FileCategories) -> f64 {
         + categories.versioned.total_size_gb
         + categories.large_unused.total_size_gb
         + categories.temporary.total_size_gb
+        + categories.build_artifacts.total_size_gb
+        + categories.near_duplicates.total_size_gb
+        + categories.unpacked_archives.total_size_gb
 }
 
 fn calculate_potential_savings(categories: &FileCategories) -> f64 {
@@ -444,15 +961,34 @@ fn calculate_potential_savings(categories: &FileCategories) -> f64 {
     categories.stale.total_size_gb * 0.7 +      // Can remove many stale files
     categories.archived.total_size_gb * 0.8 +   // Can remove most archived
     categories.versioned.total_size_gb * 0.5 +  // Can remove some versions
-    categories.temporary.total_size_gb * 1.0 // Can remove all temp files
+    categories.temporary.total_size_gb * 1.0 + // Can remove all temp files
+    categories.build_artifacts.total_size_gb * 1.0 + // Rebuilt on the next install or build
+    categories.near_duplicates.total_size_gb * 0.5 + // Some look-alikes are edits worth keeping
+    categories.unpacked_archives.total_size_gb * 1.0 // Every file is still on disk elsewhere
 }
 
 fn generate_recommendations(
     categories: &FileCategories,
     _scores: &HashMap<String, f64>,
+    coverage_percent: Option<f64>,
 ) -> Vec<Recommendation> {
     let mut recommendations = Vec::new();
 
+    // Scan coverage: the score only reflects what could be read
+    if let Some(coverage) = coverage_percent.filter(|c| *c < 100.0) {
+        recommendations.push(Recommendation {
+            priority: if coverage < 90.0 { Priority::High } else { Priority::Low },
+            category: "Scan Coverage".to_string(),
+            action: "Rescan with access to the unreadable folders".to_string(),
+            impact_gb: 0.0,
+            effort: EffortLevel::Easy,
+            details: format!(
+                "Only {:.1}% of local paths could be read, so bloat in the rest is missing from this score. See the scan's errors for which paths failed.",
+                coverage
+            ),
+        });
+    }
+
     // Duplicates recommendation
     if categories.duplicates.count > 0 {
         recommendations.push(Recommendation {
@@ -468,6 +1004,50 @@ fn generate_recommendations(
         });
     }
 
+    // Near-duplicates recommendation
+    if categories.near_duplicates.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.near_duplicates.total_size_gb > 5.0 { Priority::High } else { Priority::Medium },
+            category: "Near-Duplicates".to_string(),
+            action: "Review look-alike copies".to_string(),
+            impact_gb: categories.near_duplicates.total_size_gb * 0.5,
+            effort: EffortLevel::Moderate,
+            details: format!(
+                "{} resized or re-encoded images and lightly edited documents take up {:.2} GB. Compare them with the copy each group keeps before removing.",
+                categories.near_duplicates.count, categories.near_duplicates.total_size_gb
+            ),
+        });
+    }
+
+    // Unpacked archives recommendation
+    if categories.unpacked_archives.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.unpacked_archives.total_size_gb > 1.0 { Priority::High } else { Priority::Medium },
+            category: "Unpacked Archives".to_string(),
+            action: "Delete archives that were already extracted".to_string(),
+            impact_gb: categories.unpacked_archives.total_size_gb,
+            effort: EffortLevel::Easy,
+            details: format!(
+                "{} archives and repeated extractions take up {:.2} GB while every file in them also exists unpacked.",
+                categories.unpacked_archives.count, categories.unpacked_archives.total_size_gb
+            ),
+        });
+    }
+
+    if categories.structural_junk.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.structural_junk.count > 100 { Priority::Medium } else { Priority::Low },
+            category: "Structural Junk".to_string(),
+            action: "Remove empty folders, empty files and broken links".to_string(),
+            impact_gb: 0.0,
+            effort: EffortLevel::Easy,
+            details: format!(
+                "{} empty folders, zero-byte files and broken symlinks clutter the tree. Empty folder chains can be removed in one step.",
+                categories.structural_junk.count
+            ),
+        });
+    }
+
     // Stale files recommendation
     if categories.stale.count > 0 {
         recommendations.push(Recommendation {
@@ -498,6 +1078,51 @@ fn generate_recommendations(
         });
     }
 
+    // Temp and cache files recommendation
+    if categories.temporary.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.temporary.total_size_gb > 1.0 { Priority::High } else { Priority::Low },
+            category: "Temporary Files".to_string(),
+            action: "Clear temp and cache files".to_string(),
+            impact_gb: categories.temporary.total_size_gb,
+            effort: EffortLevel::Easy,
+            details: format!(
+                "{} temp, backup, crash dump and cache files take up {:.2} GB. Apps recreate caches as needed.",
+                categories.temporary.count, categories.temporary.total_size_gb
+            ),
+        });
+    }
+
+    // Versioned files recommendation
+    if categories.versioned.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.versioned.total_size_gb > 1.0 { Priority::Medium } else { Priority::Low },
+            category: "Versioned Files".to_string(),
+            action: "Prune old versions".to_string(),
+            impact_gb: categories.versioned.total_size_gb * 0.5,
+            effort: EffortLevel::Moderate,
+            details: format!(
+                "{} old versions and inactive forks take up {:.2} GB. Keep the newest copy of each and remove the rest.",
+                categories.versioned.count, categories.versioned.total_size_gb
+            ),
+        });
+    }
+
+    // Build artifacts recommendation
+    if categories.build_artifacts.count > 0 {
+        recommendations.push(Recommendation {
+            priority: if categories.build_artifacts.total_size_gb > 5.0 { Priority::High } else { Priority::Medium },
+            category: "Build Artifacts".to_string(),
+            action: "Delete regenerable build output".to_string(),
+            impact_gb: categories.build_artifacts.total_size_gb,
+            effort: EffortLevel::Easy,
+            details: format!(
+                "{} node_modules, target, build or dist folders take up {:.2} GB. Their projects can rebuild them with an install or build.",
+                categories.build_artifacts.count, categories.build_artifacts.total_size_gb
+            ),
+        });
+    }
+
     // Sort by priority and impact
     recommendations.sort_by(|a, b| match (&a.priority, &b.priority) {
         (Priority::Critical, Priority::Critical) => b.impact_gb.partial_cmp(&a.impact_gb).unwrap(),
@@ -519,6 +1144,8 @@ impl Default for CategoryStats {
             total_size_gb: 0.0,
             percentage_of_total: 0.0,
             items: Vec::new(),
+            local_paths: Vec::new(),
+            regenerable: false,
         }
     }
 }
@@ -532,6 +1159,10 @@ impl Default for FileCategories {
             archived: CategoryStats::default(),
             large_unused: CategoryStats::default(),
             temporary: CategoryStats::default(),
+            build_artifacts: CategoryStats::default(),
+            near_duplicates: CategoryStats::default(),
+            unpacked_archives: CategoryStats::default(),
+            structural_junk: CategoryStats::default(),
         }
     }
 }
@@ -0,0 +1,326 @@
+// src-tauri/src/cleanup/dedupe.rs
+use super::ActionFailure;
+use crate::scanners::local_scanner::DuplicateGroup;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "desktop")]
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkMode {
+    #[default]
+    Auto, // Reflink where the filesystem supports it, otherwise hardlink
+    Hardlink,
+    Reflink, // Copy-on-write clone, e.g. on btrfs or XFS
+}
+
+impl std::str::FromStr for LinkMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(LinkMode::Auto),
+            "hardlink" => Ok(LinkMode::Hardlink),
+            "reflink" => Ok(LinkMode::Reflink),
+            _ => Err(format!("Unknown link mode: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Hardlink,
+    Reflink,
+    AlreadyLinked, // Both paths were already the same file
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DedupeOutcome {
+    pub hardlinked: usize,
+    pub reflinked: usize,
+    pub already_linked: usize,
+    pub bytes_reclaimed: u64,
+    pub failures: Vec<ActionFailure>,
+}
+
+/// Replaces every copy but the first in each group with a link to the first,
+/// so every path keeps working while the data is stored once
+pub fn dedupe_groups(groups: &[DuplicateGroup], mode: LinkMode) -> DedupeOutcome {
+    let mut outcome = DedupeOutcome::default();
+
+    for group in groups {
+        let Some(keep) = group.files.first() else {
+            continue;
+        };
+        for file in group.files.iter().skip(1) {
+            match link_duplicate(Path::new(&keep.path), Path::new(&file.path), mode) {
+                Ok(LinkKind::Hardlink) => outcome.hardlinked += 1,
+                Ok(LinkKind::Reflink) => outcome.reflinked += 1,
+                Ok(LinkKind::AlreadyLinked) => {
+                    outcome.already_linked += 1;
+                    continue;
+                }
+                Err(e) => {
+                    outcome.failures.push(ActionFailure::new(&file.path, e));
+                    continue;
+                }
+            }
+            outcome.bytes_reclaimed += file.size;
+        }
+    }
+
+    outcome
+}
+
+/// Replaces `duplicate` with a link to `keep` after checking that both hold
+/// the same bytes. Hardlinks share one inode, so they are only made when both
+/// files already have the same permissions, owner and modification time;
+/// reflinks keep the duplicate's own permissions and timestamps.
+pub fn link_duplicate(keep: &Path, duplicate: &Path, mode: LinkMode) -> Result<LinkKind, String> {
+    let keep_meta = regular_file(keep)?;
+    let duplicate_meta = regular_file(duplicate)?;
+
+    if same_file(&keep_meta, &duplicate_meta) {
+        return Ok(LinkKind::AlreadyLinked);
+    }
+    if !same_device(&keep_meta, &duplicate_meta) {
+        return Err("Not on the same filesystem as the kept copy".to_string());
+    }
+    if !files_identical(keep, duplicate)
+        .map_err(|e| format!("Failed to compare with the kept copy: {}", e))?
+    {
+        return Err("Contents differ from the kept copy".to_string());
+    }
+
+    if mode != LinkMode::Hardlink {
+        match replace_with_reflink(keep, duplicate, &duplicate_meta) {
+            Ok(()) => return Ok(LinkKind::Reflink),
+            Err(e) if mode == LinkMode::Reflink => {
+                return Err(format!("Failed to reflink: {}", e));
+            }
+            // Filesystem can't clone, fall back to a hardlink
+            Err(_) => {}
+        }
+    }
+
+    if !same_access(&keep_meta, &duplicate_meta) {
+        return Err(
+            "Permissions or owner differ from the kept copy, a hardlink would change them"
+                .to_string(),
+        );
+    }
+    if keep_meta.modified().ok() != duplicate_meta.modified().ok() {
+        return Err(
+            "Modification time differs from the kept copy, a hardlink would change it".to_string(),
+        );
+    }
+    replace_with_hardlink(keep, duplicate).map_err(|e| format!("Failed to hardlink: {}", e))?;
+    Ok(LinkKind::Hardlink)
+}
+
+fn regular_file(path: &Path) -> Result<fs::Metadata, String> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a regular file", path.display()));
+    }
+    Ok(metadata)
+}
+
+/// Byte-for-byte comparison; the hash that grouped them is not proof enough
+fn files_identical(a: &Path, b: &Path) -> io::Result<bool> {
+    let mut a = fs::File::open(a)?;
+    let mut b = fs::File::open(b)?;
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let mut buffer_a = [0; 8192];
+    let mut buffer_b = [0; 8192];
+    loop {
+        let read = a.read(&mut buffer_a)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        b.read_exact(&mut buffer_b[..read])?;
+        if buffer_a[..read] != buffer_b[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Replaces `duplicate` with a hard link to `keep`. The link is made next to
+/// the duplicate first, so a failure never leaves the path missing.
+pub(crate) fn replace_with_hardlink(keep: &Path, duplicate: &Path) -> io::Result<()> {
+    let temp = sibling_temp_path(duplicate);
+    fs::hard_link(keep, &temp)?;
+    fs::rename(&temp, duplicate).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+/// Replaces `duplicate` with a copy-on-write clone of `keep`, then restores
+/// the duplicate's permissions and timestamps on the clone
+fn replace_with_reflink(keep: &Path, duplicate: &Path, original: &fs::Metadata) -> io::Result<()> {
+    let temp = sibling_temp_path(duplicate);
+    let cloned = clone_file(keep, &temp).and_then(|_| {
+        let mut times = fs::FileTimes::new();
+        if let Ok(modified) = original.modified() {
+            times = times.set_modified(modified);
+        }
+        if let Ok(accessed) = original.accessed() {
+            times = times.set_accessed(accessed);
+        }
+        let clone = fs::File::options().write(true).open(&temp)?;
+        clone.set_permissions(original.permissions())?;
+        clone.set_times(times)?;
+        fs::rename(&temp, duplicate)
+    });
+
+    if cloned.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    cloned
+}
+
+#[cfg(target_os = "linux")]
+fn clone_file(from: &Path, to: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let source = fs::File::open(from)?;
+    let target = fs::File::options().write(true).create_new(true).open(to)?;
+    // SAFETY: both descriptors are open for the duration of the call
+    if unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn clone_file(from: &Path, to: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let from = CString::new(from.as_os_str().as_bytes())?;
+    let to = CString::new(to.as_os_str().as_bytes())?;
+    // SAFETY: both paths are valid NUL-terminated strings
+    if unsafe { libc::clonefile(from.as_ptr(), to.as_ptr(), 0) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn clone_file(_from: &Path, _to: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Reflinks are not supported on this platform",
+    ))
+}
+
+fn sibling_temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.stackburn-tmp", name))
+}
+
+#[cfg(unix)]
+fn same_file(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_file(_a: &fs::Metadata, _b: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn same_device(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev()
+}
+
+// Linking across volumes fails cleanly, so let the attempt decide
+#[cfg(not(unix))]
+fn same_device(_a: &fs::Metadata, _b: &fs::Metadata) -> bool {
+    true
+}
+
+#[cfg(unix)]
+fn same_access(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.mode() == b.mode() && a.uid() == b.uid() && a.gid() == b.gid()
+}
+
+#[cfg(not(unix))]
+fn same_access(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    a.permissions().readonly() == b.permissions().readonly()
+}
+
+#[cfg_attr(feature = "desktop", command)]
+pub async fn dedupe_duplicates(
+    groups: Vec<DuplicateGroup>,
+    mode: Option<LinkMode>,
+) -> Result<DedupeOutcome, String> {
+    tokio::task::spawn_blocking(move || dedupe_groups(&groups, mode.unwrap_or_default()))
+        .await
+        .map_err(|e| format!("Dedupe worker failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn write_file(path: &Path, contents: &str, modified: SystemTime) {
+        fs::write(path, contents).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_times(fs::FileTimes::new().set_modified(modified))
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hardlinks_identical_copies() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(60);
+        let keep = dir.path().join("photo.jpg");
+        let duplicate = dir.path().join("photo (1).jpg");
+        write_file(&keep, "same bytes", modified);
+        write_file(&duplicate, "same bytes", modified);
+
+        let kind = link_duplicate(&keep, &duplicate, LinkMode::Hardlink).unwrap();
+        assert_eq!(kind, LinkKind::Hardlink);
+        let inode = |path: &Path| fs::metadata(path).unwrap().ino();
+        assert_eq!(inode(&keep), inode(&duplicate));
+        assert_eq!(
+            link_duplicate(&keep, &duplicate, LinkMode::Hardlink).unwrap(),
+            LinkKind::AlreadyLinked
+        );
+    }
+
+    #[test]
+    fn refuses_copies_that_differ() {
+        let dir = tempfile::tempdir().unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(60);
+        let keep = dir.path().join("notes.txt");
+        let duplicate = dir.path().join("notes copy.txt");
+        write_file(&keep, "first draft", modified);
+        write_file(&duplicate, "final draft", modified);
+
+        assert!(link_duplicate(&keep, &duplicate, LinkMode::Auto).is_err());
+        assert_eq!(fs::read_to_string(&duplicate).unwrap(), "final draft");
+    }
+}
@@ -0,0 +1,113 @@
+// src-tauri/src/cleanup/empty_dirs.rs
+use super::ActionFailure;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "desktop")]
+use tauri::command;
+
+// Version control keeps empty folders it relies on, e.g. .git/refs in a new repository
+const SKIPPED_DIRS: &[&str] = &[".git", ".hg", ".svn"];
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct EmptyDirOutcome {
+    pub removed: Vec<String>, // Deepest first, in the order they were removed
+    pub failures: Vec<ActionFailure>,
+}
+
+/// Removes each folder in `paths` together with the empty folders below it,
+/// innermost first. A listed folder is only touched when everything below it
+/// is still empty folders; one that gained content since the scan is left
+/// as it is and reported as a failure.
+pub fn remove_empty_directories(paths: &[String]) -> EmptyDirOutcome {
+    let mut outcome = EmptyDirOutcome::default();
+
+    for path in paths {
+        let dir = Path::new(path);
+        let is_dir = fs::symlink_metadata(dir).is_ok_and(|m| m.is_dir());
+        if !is_dir {
+            outcome
+                .failures
+                .push(ActionFailure::new(path, "Not a directory"));
+            continue;
+        }
+
+        let mut chain = Vec::new();
+        if let Err(e) = empty_chain(dir, &mut chain) {
+            outcome.failures.push(ActionFailure::new(path, e));
+            continue;
+        }
+        // remove_dir refuses a folder that gained an entry since it was checked
+        for folder in chain {
+            if let Err(e) = fs::remove_dir(&folder) {
+                outcome.failures.push(ActionFailure::new(
+                    folder.to_string_lossy(),
+                    format!("Failed to remove directory: {}", e),
+                ));
+                break;
+            }
+            outcome.removed.push(folder.to_string_lossy().to_string());
+        }
+    }
+
+    outcome
+}
+
+/// Collects `dir` and every folder below it, deepest first, without removing
+/// anything. Fails as soon as one of them holds something other than folders.
+fn empty_chain(dir: &Path, chain: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        // Symlinks to folders are content, not folders to descend into
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        let skipped = SKIPPED_DIRS.iter().any(|name| entry.file_name() == *name);
+        if !is_dir || skipped {
+            return Err("Directory is not empty".to_string());
+        }
+        empty_chain(&entry.path(), chain)?;
+    }
+
+    chain.push(dir.to_path_buf());
+    Ok(())
+}
+
+#[cfg_attr(feature = "desktop", command)]
+pub async fn remove_empty_dirs(paths: Vec<String>) -> Result<EmptyDirOutcome, String> {
+    tokio::task::spawn_blocking(move || remove_empty_directories(&paths))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_nested_empty_folders_deepest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let top = dir.path().join("empty");
+        fs::create_dir_all(top.join("a/b")).unwrap();
+
+        let outcome = remove_empty_directories(&[top.to_string_lossy().to_string()]);
+        assert!(outcome.failures.is_empty());
+        assert_eq!(outcome.removed.len(), 3);
+        assert!(outcome.removed[0].ends_with("b"));
+        assert!(!top.exists());
+    }
+
+    #[test]
+    fn leaves_folders_that_gained_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let top = dir.path().join("was-empty");
+        fs::create_dir_all(top.join("a/b")).unwrap();
+        fs::write(top.join("a/b/new.txt"), "").unwrap();
+
+        let outcome = remove_empty_directories(&[top.to_string_lossy().to_string()]);
+        assert!(outcome.removed.is_empty());
+        assert_eq!(outcome.failures.len(), 1);
+        assert!(top.join("a/b/new.txt").exists());
+    }
+}
@@ -0,0 +1,148 @@
+// src-tauri/src/cleanup/mod.rs
+pub mod dedupe;
+pub mod empty_dirs;
+pub mod plan;
+pub mod quarantine;
+pub mod trash;
+
+use crate::scanners::local_scanner::DuplicateGroup;
+use quarantine::Quarantine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+use trash::Trash;
+
+/// A path an action could not be applied to, and why
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActionFailure {
+    pub path: String,
+    pub error: String,
+}
+
+impl ActionFailure {
+    pub fn new(path: impl Into<String>, error: impl Into<String>) -> Self {
+        ActionFailure {
+            path: path.into(),
+            error: error.into(),
+        }
+    }
+}
+
+/// Where deleted files go. Either way they can be restored.
+#[derive(Clone, Copy)]
+pub enum Disposal<'a> {
+    Quarantine(&'a Quarantine),
+    Trash(&'a Trash), // The desktop trash, restorable from the file manager too
+}
+
+/// Every copy in `groups` except the first of each group, which is kept.
+/// Groups whose kept file has disappeared since the scan are skipped.
+pub fn redundant_copies(groups: &[DuplicateGroup]) -> Vec<String> {
+    groups
+        .iter()
+        .filter(|group| {
+            group
+                .files
+                .first()
+                .is_some_and(|keep| Path::new(&keep.path).is_file())
+        })
+        .flat_map(|group| group.files.iter().skip(1).map(|f| f.path.clone()))
+        .collect()
+}
+
+/// Moves a file, falling back to copy-and-delete across filesystems. The
+/// copy keeps the original permissions and timestamps, and is removed again
+/// if anything after creating it fails.
+pub(crate) fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            let mut copy = fs::File::create_new(to)?;
+            let moved = copy_into(from, &mut copy).and_then(|_| fs::remove_file(from));
+            drop(copy);
+            if moved.is_err() {
+                let _ = fs::remove_file(to);
+            }
+            moved
+        }
+        result => result,
+    }
+}
+
+// Permissions go last, so a read-only original doesn't lock the copy before
+// its timestamps are set
+fn copy_into(from: &Path, copy: &mut fs::File) -> io::Result<()> {
+    let metadata = fs::metadata(from)?;
+    io::copy(&mut fs::File::open(from)?, copy)?;
+
+    let mut times = fs::FileTimes::new();
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    copy.set_times(times)?;
+    copy.set_permissions(metadata.permissions())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn read_only_file(path: &Path, contents: &str) -> SystemTime {
+        let modified = SystemTime::now() - Duration::from_secs(3600);
+        fs::write(path, contents).unwrap();
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_times(fs::FileTimes::new().set_modified(modified))
+            .unwrap();
+        let mut permissions = file.metadata().unwrap().permissions();
+        permissions.set_readonly(true);
+        file.set_permissions(permissions).unwrap();
+        modified
+    }
+
+    #[test]
+    fn move_file_moves_read_only_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("report.txt");
+        let to = dir.path().join("moved/report.txt");
+        read_only_file(&from, "quarterly numbers");
+
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "quarterly numbers");
+    }
+
+    // Needs /dev/shm on its own filesystem; skipped where it isn't
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn move_file_copies_read_only_files_across_filesystems() {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(source) = tempfile::tempdir_in("/dev/shm") else {
+            return;
+        };
+        let target = tempfile::tempdir().unwrap();
+        let source_dev = fs::metadata(source.path()).unwrap().dev();
+        if source_dev == fs::metadata(target.path()).unwrap().dev() {
+            return;
+        }
+
+        let from = source.path().join("report.txt");
+        let to = target.path().join("report.txt");
+        let modified = read_only_file(&from, "quarterly numbers");
+
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        let metadata = fs::metadata(&to).unwrap();
+        assert!(metadata.permissions().readonly());
+        assert_eq!(metadata.modified().unwrap(), modified);
+        assert_eq!(fs::read_to_string(&to).unwrap(), "quarterly numbers");
+    }
+}
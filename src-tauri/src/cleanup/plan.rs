@@ -0,0 +1,656 @@
+// src-tauri/src/cleanup/plan.rs
+use super::dedupe::{link_duplicate, LinkMode};
+use super::{move_file, ActionFailure, Disposal};
+#[cfg(feature = "desktop")]
+use super::{quarantine::Quarantine, trash::Trash};
+use crate::burn_score::BurnScoreResult;
+use crate::scanners::duplicates::calculate_file_hash;
+use crate::scanners::local_scanner::{FolderStats, LocalFile};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
+
+const PLAN_VERSION: u32 = 1;
+const UNUSED_REASON: &str = "Not used in 6+ months";
+const TEMPORARY_REASON: &str = "Temp or cache file, recreated on demand";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    Delete,   // Into quarantine or the desktop trash, so it can still be restored
+    Move,     // To `target`
+    Hardlink, // Replace with a hard link to the kept copy
+    Archive,  // Gzip to `target`, then remove the original
+}
+
+impl std::str::FromStr for PlanAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "delete" => Ok(PlanAction::Delete),
+            "move" => Ok(PlanAction::Move),
+            "hardlink" => Ok(PlanAction::Hardlink),
+            "archive" => Ok(PlanAction::Archive),
+            _ => Err(format!("Unknown cleanup action: {}", s)),
+        }
+    }
+}
+
+/// One file a plan proposes to clean up, with what it looked like when the
+/// plan was made
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlannedAction {
+    pub action: PlanAction,
+    pub path: String,
+    pub target: Option<String>, // Destination of a move or archive
+    pub keep: Option<String>,   // For duplicates, the copy that must still exist
+    pub size_bytes: u64,        // Expected bytes reclaimed; less for archives
+    pub modified_time: Option<DateTime<Utc>>,
+    pub hash: Option<String>, // SHA-256 when the scan had one
+    pub reason: String,
+}
+
+/// Reviewable list of cleanup actions. Nothing is touched until it is applied.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupPlan {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub source: String, // What the plan was generated from
+    pub total_bytes: u64,
+    pub actions: Vec<PlannedAction>,
+}
+
+/// Which actions a generated plan proposes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PlanOptions {
+    pub duplicates: Option<PlanAction>, // Delete or hardlink redundant copies
+    pub unused: Option<PlanAction>,     // Files not used in 6+ months
+    pub temporary: Option<PlanAction>,  // Temp and cache files, recreated on demand
+    pub destination: Option<String>,    // Where moved and archived files go
+}
+
+impl Default for PlanOptions {
+    fn default() -> Self {
+        PlanOptions {
+            duplicates: Some(PlanAction::Delete),
+            unused: None,
+            temporary: None,
+            destination: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanFormat {
+    Json,
+    Toml,
+}
+
+impl PlanFormat {
+    /// TOML for `.toml` files, JSON for anything else
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => PlanFormat::Toml,
+            _ => PlanFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PlanReport {
+    pub dry_run: bool,
+    pub applied: usize, // Actions that passed verification and were (or would be) applied
+    pub bytes_reclaimed: u64,
+    pub drifted: Vec<ActionFailure>, // Changed since the plan was made, so left alone
+    pub failures: Vec<ActionFailure>,
+    pub quarantine_batch: Option<String>, // Where deleted files went, unless they were trashed
+}
+
+impl CleanupPlan {
+    /// Proposes actions for the duplicates, unused and temporary files of a
+    /// local scan. Only the largest temporary files the scan lists are covered.
+    pub fn from_folder_stats(stats: &FolderStats, options: &PlanOptions) -> Result<Self, String> {
+        options.validate()?;
+        let mut actions = Vec::new();
+        let mut planned = HashSet::new();
+
+        // The copy each duplicate group keeps is never cleaned up itself
+        let kept: HashSet<&str> = stats
+            .duplicates
+            .iter()
+            .filter_map(|group| group.files.first())
+            .map(|file| file.path.as_str())
+            .collect();
+
+        if let Some(action) = options.duplicates {
+            for group in &stats.duplicates {
+                let Some(keep) = group.files.first() else {
+                    continue;
+                };
+                for file in group.files.iter().skip(1) {
+                    if kept.contains(file.path.as_str()) || !planned.insert(file.path.as_str()) {
+                        continue;
+                    }
+                    actions.push(PlannedAction {
+                        action,
+                        path: file.path.clone(),
+                        target: None,
+                        keep: Some(keep.path.clone()),
+                        size_bytes: file.size,
+                        modified_time: Some(file.modified_time),
+                        hash: Some(group.hash.clone()),
+                        reason: format!("Duplicate of {}", keep.path),
+                    });
+                }
+            }
+        }
+
+        if let Some(action) = options.unused {
+            for file in &stats.unused_files {
+                if !kept.contains(file.path.as_str()) && planned.insert(file.path.as_str()) {
+                    actions.push(unused_action(action, file, options));
+                }
+            }
+        }
+
+        if let Some(action) = options.temporary {
+            for file in &stats.temporary.largest {
+                if kept.contains(file.path.as_str()) || !planned.insert(file.path.as_str()) {
+                    continue;
+                }
+                actions.extend(local_action(action, &file.path, TEMPORARY_REASON, options));
+            }
+        }
+
+        Ok(Self::new(
+            format!("Local scan from {}", stats.scan_timestamp.to_rfc3339()),
+            actions,
+        ))
+    }
+
+    /// Proposes actions for the unused and temporary local files a burn
+    /// score counted. Duplicates are left out, since the score doesn't say
+    /// which copy is kept, so `options.duplicates` has no effect here.
+    pub fn from_burn_score(
+        result: &BurnScoreResult,
+        options: &PlanOptions,
+    ) -> Result<Self, String> {
+        options.validate()?;
+
+        let categories = &result.file_categories;
+        let mut actions = Vec::new();
+        let mut planned = HashSet::new();
+        for (action, stats, reason) in [
+            (options.unused, &categories.stale, UNUSED_REASON),
+            (options.temporary, &categories.temporary, TEMPORARY_REASON),
+        ] {
+            let Some(action) = action else {
+                continue;
+            };
+            // Only files from local scans; other sources list names and URLs
+            for path in &stats.local_paths {
+                if planned.insert(path.as_str()) {
+                    actions.extend(local_action(action, path, reason, options));
+                }
+            }
+        }
+
+        Ok(Self::new(
+            format!("Burn score from {}", result.calculated_at.to_rfc3339()),
+            actions,
+        ))
+    }
+
+    fn new(source: String, actions: Vec<PlannedAction>) -> Self {
+        CleanupPlan {
+            version: PLAN_VERSION,
+            created_at: Utc::now(),
+            source,
+            total_bytes: actions.iter().map(|a| a.size_bytes).sum(),
+            actions,
+        }
+    }
+
+    pub fn render(&self, format: PlanFormat) -> Result<String, String> {
+        match format {
+            PlanFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize plan: {}", e)),
+            PlanFormat::Toml => {
+                toml::to_string_pretty(self).map_err(|e| format!("Failed to serialize plan: {}", e))
+            }
+        }
+    }
+
+    pub fn parse(contents: &str, format: PlanFormat) -> Result<Self, String> {
+        let plan: CleanupPlan = match format {
+            PlanFormat::Json => serde_json::from_str(contents)
+                .map_err(|e| format!("Failed to parse plan: {}", e))?,
+            PlanFormat::Toml => {
+                toml::from_str(contents).map_err(|e| format!("Failed to parse plan: {}", e))?
+            }
+        };
+
+        if plan.version > PLAN_VERSION {
+            return Err(format!(
+                "Plan version {} is newer than this version of StackBurn supports",
+                plan.version
+            ));
+        }
+        Ok(plan)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = self.render(PlanFormat::from_path(path))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&contents, PlanFormat::from_path(path))
+    }
+}
+
+impl PlanOptions {
+    fn validate(&self) -> Result<(), String> {
+        if matches!(
+            self.duplicates,
+            Some(PlanAction::Move | PlanAction::Archive)
+        ) {
+            return Err("Duplicates can only be deleted or hardlinked".to_string());
+        }
+        if self.unused == Some(PlanAction::Hardlink) {
+            return Err("Unused files have nothing to hardlink to".to_string());
+        }
+        if self.temporary == Some(PlanAction::Hardlink) {
+            return Err("Temporary files have nothing to hardlink to".to_string());
+        }
+        let moves = |action| matches!(action, Some(PlanAction::Move | PlanAction::Archive));
+        if (moves(self.unused) || moves(self.temporary)) && self.destination.is_none() {
+            return Err("Moving or archiving files needs a destination directory".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn unused_action(action: PlanAction, file: &LocalFile, options: &PlanOptions) -> PlannedAction {
+    PlannedAction {
+        action,
+        path: file.path.clone(),
+        target: destination_for(action, Path::new(&file.path), options),
+        keep: None,
+        size_bytes: file.size,
+        modified_time: Some(file.modified_time),
+        hash: file.hash.clone(),
+        reason: UNUSED_REASON.to_string(),
+    }
+}
+
+/// Action for a file known only by its path, described by how it looks now.
+/// Skips anything that isn't an absolute path to a regular file.
+fn local_action(
+    action: PlanAction,
+    path: &str,
+    reason: &str,
+    options: &PlanOptions,
+) -> Option<PlannedAction> {
+    if !Path::new(path).is_absolute() {
+        return None;
+    }
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+
+    Some(PlannedAction {
+        action,
+        path: path.to_string(),
+        target: destination_for(action, Path::new(path), options),
+        keep: None,
+        size_bytes: metadata.len(),
+        modified_time: metadata.modified().ok().map(DateTime::<Utc>::from),
+        hash: None,
+        reason: reason.to_string(),
+    })
+}
+
+/// Where a moved or archived file goes: its full path recreated under the
+/// destination, so files from different folders never collide
+fn destination_for(action: PlanAction, path: &Path, options: &PlanOptions) -> Option<String> {
+    let destination = Path::new(options.destination.as_ref()?);
+    let relative: PathBuf = path
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    let target = destination.join(relative);
+
+    match action {
+        PlanAction::Move => Some(target.to_string_lossy().to_string()),
+        PlanAction::Archive => Some(format!("{}.gz", target.to_string_lossy())),
+        PlanAction::Delete | PlanAction::Hardlink => None,
+    }
+}
+
+/// Applies every action whose file still matches the plan. With `dry_run`
+/// only the checks run, so the report shows what would happen.
+pub fn apply_plan(
+    plan: &CleanupPlan,
+    disposal: Disposal<'_>,
+    dry_run: bool,
+) -> Result<PlanReport, String> {
+    let mut report = PlanReport {
+        dry_run,
+        ..PlanReport::default()
+    };
+    let mut deletions = Vec::new();
+    let kept: HashSet<&str> = plan
+        .actions
+        .iter()
+        .filter_map(|a| a.keep.as_deref())
+        .collect();
+    // Kept copies are shared by a whole duplicate group, so each is hashed once
+    let mut kept_hashes = HashMap::new();
+
+    for action in &plan.actions {
+        // An edited plan could clean up the copy another action relies on
+        if kept.contains(action.path.as_str()) {
+            report.failures.push(ActionFailure::new(
+                &action.path,
+                "Kept as the remaining copy for another action",
+            ));
+            continue;
+        }
+        if let Err(drift) = check_drift(action, &mut kept_hashes) {
+            report.drifted.push(ActionFailure::new(&action.path, drift));
+            continue;
+        }
+        if dry_run {
+            report.applied += 1;
+            report.bytes_reclaimed += action.size_bytes;
+            continue;
+        }
+
+        let path = Path::new(&action.path);
+        let applied = match (action.action, &action.target, &action.keep) {
+            // Deleted together so they share one quarantine batch
+            (PlanAction::Delete, _, _) => {
+                deletions.push(action.path.clone());
+                continue;
+            }
+            (PlanAction::Hardlink, _, Some(keep)) => {
+                link_duplicate(Path::new(keep), path, LinkMode::Hardlink).map(|_| action.size_bytes)
+            }
+            (PlanAction::Move, Some(target), _) => move_file(path, Path::new(target))
+                .map(|_| action.size_bytes)
+                .map_err(|e| format!("Failed to move file: {}", e)),
+            (PlanAction::Archive, Some(target), _) => archive_file(path, Path::new(target))
+                .map_err(|e| format!("Failed to archive file: {}", e)),
+            _ => Err("Plan entry is missing its target".to_string()),
+        };
+
+        match applied {
+            Ok(bytes) => {
+                report.applied += 1;
+                report.bytes_reclaimed += bytes;
+            }
+            Err(e) => report.failures.push(ActionFailure::new(&action.path, e)),
+        }
+    }
+
+    if deletions.is_empty() {
+        return Ok(report);
+    }
+    match disposal {
+        Disposal::Quarantine(quarantine) => {
+            let reason = format!("Cleanup plan: {}", plan.source);
+            let outcome = quarantine.quarantine(&deletions, Some(reason))?;
+            if let Some(batch) = outcome.batch {
+                report.applied += batch.items.len();
+                report.bytes_reclaimed += batch.total_bytes;
+                report.quarantine_batch = Some(batch.id);
+            }
+            report.failures.extend(outcome.failures);
+        }
+        Disposal::Trash(trash) => {
+            let outcome = trash.trash(&deletions)?;
+            report.applied += outcome.trashed.len();
+            report.bytes_reclaimed += outcome.trashed.iter().map(|i| i.size_bytes).sum::<u64>();
+            report.failures.extend(outcome.failures);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Why `action` no longer matches the file on disk, if it doesn't
+fn check_drift(
+    action: &PlannedAction,
+    kept_hashes: &mut HashMap<String, Option<String>>,
+) -> Result<(), String> {
+    let path = Path::new(&action.path);
+    let metadata = fs::symlink_metadata(path).map_err(|_| "File no longer exists".to_string())?;
+    if !metadata.is_file() {
+        return Err("No longer a regular file".to_string());
+    }
+    if metadata.len() != action.size_bytes {
+        return Err(format!(
+            "Size changed from {} to {} bytes",
+            action.size_bytes,
+            metadata.len()
+        ));
+    }
+    let modified = metadata.modified().ok().map(DateTime::<Utc>::from);
+    if action.modified_time.is_some() && modified != action.modified_time {
+        return Err("Modified since the plan was made".to_string());
+    }
+    if let Some(hash) = &action.hash {
+        let current =
+            calculate_file_hash(path).map_err(|e| format!("Failed to hash file: {}", e))?;
+        if &current != hash {
+            return Err("Contents changed since the plan was made".to_string());
+        }
+    }
+
+    if let Some(keep) = &action.keep {
+        let kept = Path::new(keep);
+        let unchanged = fs::metadata(kept)
+            .is_ok_and(|m| m.is_file() && m.len() == action.size_bytes)
+            && action.hash.as_ref().is_none_or(|hash| {
+                kept_hashes
+                    .entry(keep.clone())
+                    .or_insert_with(|| calculate_file_hash(kept).ok())
+                    .as_ref()
+                    == Some(hash)
+            });
+        if !unchanged {
+            return Err(format!("Kept copy {} is missing or changed", keep));
+        }
+    }
+    if let Some(target) = &action.target {
+        if Path::new(target).exists() {
+            return Err(format!("Target {} already exists", target));
+        }
+    }
+
+    Ok(())
+}
+
+/// Gzips `from` into `to` and removes the original. Returns the bytes saved.
+fn archive_file(from: &Path, to: &Path) -> io::Result<u64> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let metadata = fs::metadata(from)?;
+    let output = fs::File::options().write(true).create_new(true).open(to)?;
+
+    let compressed = (|| {
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut fs::File::open(from)?, &mut encoder)?;
+        let output = encoder.finish()?;
+        output.sync_all()?;
+        if let Ok(modified) = metadata.modified() {
+            output.set_modified(modified)?;
+        }
+        Ok(output.metadata()?.len())
+    })()
+    .inspect_err(|_: &io::Error| {
+        let _ = fs::remove_file(to);
+    })?;
+
+    fs::remove_file(from)?;
+    Ok(metadata.len().saturating_sub(compressed))
+}
+
+/// Builds a plan from either a local scan or a burn score
+#[cfg_attr(feature = "desktop", command)]
+pub async fn create_cleanup_plan(
+    local_data: Option<FolderStats>,
+    burn_score: Option<BurnScoreResult>,
+    options: Option<PlanOptions>,
+) -> Result<CleanupPlan, String> {
+    let options = options.unwrap_or_default();
+    match (local_data, burn_score) {
+        (Some(stats), None) => CleanupPlan::from_folder_stats(&stats, &options),
+        (None, Some(result)) => CleanupPlan::from_burn_score(&result, &options),
+        _ => Err("Pass either a local scan or a burn score".to_string()),
+    }
+}
+
+#[cfg_attr(feature = "desktop", command)]
+pub async fn save_cleanup_plan(plan: CleanupPlan, path: String) -> Result<(), String> {
+    plan.save(Path::new(&path))
+}
+
+#[cfg_attr(feature = "desktop", command)]
+pub async fn load_cleanup_plan(path: String) -> Result<CleanupPlan, String> {
+    CleanupPlan::load(Path::new(&path))
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn apply_cleanup_plan(
+    plan: CleanupPlan,
+    dry_run: Option<bool>,
+    use_trash: Option<bool>,
+    quarantine: State<'_, Quarantine>,
+    trash: State<'_, Trash>,
+) -> Result<PlanReport, String> {
+    let quarantine = quarantine.inner().clone();
+    let trash = trash.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let disposal = if use_trash.unwrap_or(false) {
+            Disposal::Trash(&trash)
+        } else {
+            Disposal::Quarantine(&quarantine)
+        };
+        apply_plan(&plan, disposal, dry_run.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn_score::{compute_burn_score, BurnScoreInput};
+    use crate::cleanup::quarantine::Quarantine;
+    use crate::scanners::local_scanner::{scan_path, ScanOptions};
+    use crate::scanners::progress::ScanMonitor;
+    use std::time::{Duration, SystemTime};
+
+    async fn scan(dir: &Path) -> FolderStats {
+        scan_path(
+            dir.to_str().unwrap(),
+            None,
+            None,
+            &ScanOptions::default(),
+            &ScanMonitor::detached(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn duplicates_plan(dir: &Path) -> CleanupPlan {
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(dir.join(name), "same bytes").unwrap();
+        }
+        let plan = CleanupPlan::from_folder_stats(&scan(dir).await, &PlanOptions::default());
+        plan.unwrap()
+    }
+
+    #[tokio::test]
+    async fn apply_plan_skips_files_changed_since_the_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = duplicates_plan(dir.path()).await;
+        assert_eq!(plan.actions.len(), 2);
+        let changed = Path::new(&plan.actions[0].path);
+        let removed = Path::new(&plan.actions[1].path);
+        fs::write(changed, "SAME BYTES").unwrap();
+
+        let quarantine = Quarantine::open(dir.path().join("quarantine")).unwrap();
+        let report = apply_plan(&plan, Disposal::Quarantine(&quarantine), false).unwrap();
+        assert_eq!(report.drifted.len(), 1);
+        assert_eq!(report.drifted[0].path, plan.actions[0].path);
+        assert_eq!(report.applied, 1);
+        assert!(changed.exists());
+        assert!(!removed.exists());
+    }
+
+    #[tokio::test]
+    async fn apply_plan_skips_duplicates_whose_kept_copy_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = duplicates_plan(dir.path()).await;
+        let keep = plan.actions[0].keep.clone().unwrap();
+        fs::write(&keep, "SAME BYTES").unwrap();
+
+        let quarantine = Quarantine::open(dir.path().join("quarantine")).unwrap();
+        let report = apply_plan(&plan, Disposal::Quarantine(&quarantine), false).unwrap();
+        assert_eq!(report.drifted.len(), 2);
+        assert_eq!(report.applied, 0);
+        assert!(plan.actions.iter().all(|a| Path::new(&a.path).exists()));
+    }
+
+    #[tokio::test]
+    async fn burn_score_plan_covers_unused_local_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old-report.txt");
+        fs::write(&old, "last touched years ago").unwrap();
+        let year_ago = SystemTime::now() - Duration::from_secs(365 * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_times(fs::FileTimes::new().set_accessed(year_ago))
+            .unwrap();
+
+        let stats = scan(dir.path()).await;
+        let score = compute_burn_score(BurnScoreInput {
+            drive_data: None,
+            local_data: Some(stats),
+            github_data: None,
+            artifact_data: None,
+            items: Vec::new(),
+        })
+        .unwrap();
+
+        let options = PlanOptions {
+            duplicates: None,
+            unused: Some(PlanAction::Delete),
+            temporary: None,
+            destination: None,
+        };
+        let plan = CleanupPlan::from_burn_score(&score, &options).unwrap();
+        let paths: Vec<&str> = plan.actions.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, [old.to_str().unwrap()]);
+    }
+}
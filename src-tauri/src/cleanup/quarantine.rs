@@ -0,0 +1,527 @@
+// src-tauri/src/cleanup/quarantine.rs
+use super::{move_file, redundant_copies, ActionFailure};
+use crate::scanners::local_scanner::DuplicateGroup;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
+
+pub const QUARANTINE_DIR_NAME: &str = "quarantine";
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const DEFAULT_RETENTION_DAYS: u32 = 30;
+const MAX_RETENTION_DAYS: u32 = 3650;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuarantinedItem {
+    pub original_path: String,
+    pub stored_name: String, // File name inside the batch, so equal names never collide
+    pub size_bytes: u64,
+    pub modified_time: Option<DateTime<Utc>>,
+    pub restored: bool,
+}
+
+/// One cleanup action's worth of quarantined files, restorable as a unit
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuarantineBatch {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub reason: Option<String>, // e.g. "Remove duplicate files"
+    pub items: Vec<QuarantinedItem>,
+    pub total_bytes: u64,
+    pub expires_at: DateTime<Utc>, // Purged permanently after this
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarantineOutcome {
+    pub batch: Option<QuarantineBatch>, // None when nothing could be moved
+    pub failures: Vec<ActionFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreOutcome {
+    pub batch_id: String,
+    pub restored: usize,
+    pub failures: Vec<ActionFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PurgeOutcome {
+    pub batches_purged: usize,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuarantineSettings {
+    pub retention_days: u32,
+}
+
+impl Default for QuarantineSettings {
+    fn default() -> Self {
+        QuarantineSettings {
+            retention_days: DEFAULT_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Holding area for files StackBurn cleans up. Each batch lives in its own
+/// directory with a manifest of where every file came from. Clones share
+/// one lock, so commands can move it onto a blocking worker.
+#[derive(Clone)]
+pub struct Quarantine {
+    root: PathBuf,
+    settings: Arc<Mutex<QuarantineSettings>>, // Also serializes batch changes
+}
+
+impl Quarantine {
+    pub fn open(root: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&root)
+            .map_err(|e| format!("Failed to create quarantine directory: {}", e))?;
+
+        let settings: QuarantineSettings = match fs::read_to_string(root.join(SETTINGS_FILE_NAME)) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to parse quarantine settings: {}", e))?,
+            Err(_) => QuarantineSettings::default(),
+        };
+        validate_retention_days(settings.retention_days)?;
+
+        Ok(Quarantine {
+            root,
+            settings: Arc::new(Mutex::new(settings)),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn settings(&self) -> Result<QuarantineSettings, String> {
+        Ok(self.lock()?.clone())
+    }
+
+    pub fn set_retention_days(&self, retention_days: u32) -> Result<QuarantineSettings, String> {
+        validate_retention_days(retention_days)?;
+        let mut settings = self.lock()?;
+        let updated = QuarantineSettings { retention_days };
+        write_json(&self.root.join(SETTINGS_FILE_NAME), &updated)?;
+        *settings = updated.clone();
+        Ok(updated)
+    }
+
+    /// Moves `paths` into a new batch. Paths that cannot be moved are
+    /// reported and left where they are.
+    pub fn quarantine(
+        &self,
+        paths: &[String],
+        reason: Option<String>,
+    ) -> Result<QuarantineOutcome, String> {
+        let settings = self.lock()?;
+        let created_at = Utc::now();
+        let id = self.new_batch_id(created_at);
+        let batch_dir = self.root.join(&id);
+        let mut failures = Vec::new();
+
+        // Record the plan before touching anything, so a crash mid-way can still be restored
+        let mut items = Vec::new();
+        for path in paths {
+            match self.check_movable(Path::new(path)) {
+                Ok(metadata) => items.push(QuarantinedItem {
+                    original_path: path.clone(),
+                    stored_name: format!("{:06}", items.len()),
+                    size_bytes: metadata.len(),
+                    modified_time: metadata.modified().ok().map(DateTime::<Utc>::from),
+                    restored: false,
+                }),
+                Err(e) => failures.push(ActionFailure::new(path, e)),
+            }
+        }
+        if items.is_empty() {
+            return Ok(QuarantineOutcome {
+                batch: None,
+                failures,
+            });
+        }
+
+        let mut batch = QuarantineBatch {
+            id,
+            created_at,
+            reason,
+            total_bytes: items.iter().map(|i| i.size_bytes).sum(),
+            items,
+            expires_at: days_after(created_at, settings.retention_days)?,
+        };
+        write_json(&batch_dir.join(MANIFEST_FILE_NAME), &batch)?;
+
+        batch.items.retain(|item| {
+            let stored = batch_dir.join(&item.stored_name);
+            match move_file(Path::new(&item.original_path), &stored) {
+                Ok(()) => true,
+                Err(e) => {
+                    failures.push(ActionFailure::new(
+                        &item.original_path,
+                        format!("Failed to move into quarantine: {}", e),
+                    ));
+                    false
+                }
+            }
+        });
+
+        if batch.items.is_empty() {
+            let _ = fs::remove_dir_all(&batch_dir);
+            return Ok(QuarantineOutcome {
+                batch: None,
+                failures,
+            });
+        }
+
+        batch.total_bytes = batch.items.iter().map(|i| i.size_bytes).sum();
+        write_json(&batch_dir.join(MANIFEST_FILE_NAME), &batch)?;
+
+        Ok(QuarantineOutcome {
+            batch: Some(batch),
+            failures,
+        })
+    }
+
+    /// Quarantines every redundant copy in `groups`, keeping the first file of each
+    pub fn quarantine_duplicates(
+        &self,
+        groups: &[DuplicateGroup],
+    ) -> Result<QuarantineOutcome, String> {
+        self.quarantine(
+            &redundant_copies(groups),
+            Some("Remove duplicate files".to_string()),
+        )
+    }
+
+    /// Lists batches newest first
+    pub fn list(&self) -> Result<Vec<QuarantineBatch>, String> {
+        let _settings = self.lock()?;
+        self.read_batches()
+    }
+
+    /// Moves every file of a batch back to where it came from. Files whose
+    /// original path has since been reused are left in quarantine.
+    pub fn restore(&self, batch_id: &str) -> Result<RestoreOutcome, String> {
+        let _settings = self.lock()?;
+        let batch_dir = self.batch_dir(batch_id)?;
+        let mut batch: QuarantineBatch = read_json(&batch_dir.join(MANIFEST_FILE_NAME))?;
+        let mut restored = 0;
+        let mut failures = Vec::new();
+
+        for item in batch.items.iter_mut().filter(|i| !i.restored) {
+            let original = Path::new(&item.original_path);
+            let stored = batch_dir.join(&item.stored_name);
+
+            if !stored.exists() {
+                // Interrupted before this file was moved, so it never left
+                item.restored = original.exists();
+                if !item.restored {
+                    failures.push(ActionFailure::new(
+                        &item.original_path,
+                        "Missing from quarantine",
+                    ));
+                }
+                continue;
+            }
+            if original.exists() {
+                failures.push(ActionFailure::new(
+                    &item.original_path,
+                    "A file already exists at the original path",
+                ));
+                continue;
+            }
+
+            match move_file(&stored, original) {
+                Ok(()) => {
+                    item.restored = true;
+                    restored += 1;
+                }
+                Err(e) => failures.push(ActionFailure::new(
+                    &item.original_path,
+                    format!("Failed to restore: {}", e),
+                )),
+            }
+        }
+
+        if batch.items.iter().all(|i| i.restored) {
+            fs::remove_dir_all(&batch_dir)
+                .map_err(|e| format!("Failed to remove restored batch {}: {}", batch_id, e))?;
+        } else {
+            write_json(&batch_dir.join(MANIFEST_FILE_NAME), &batch)?;
+        }
+
+        Ok(RestoreOutcome {
+            batch_id: batch_id.to_string(),
+            restored,
+            failures,
+        })
+    }
+
+    /// Permanently deletes batches past their expiry, or older than
+    /// `older_than_days` when given
+    pub fn purge(&self, older_than_days: Option<u32>) -> Result<PurgeOutcome, String> {
+        let _settings = self.lock()?;
+        let now = Utc::now();
+        let mut outcome = PurgeOutcome::default();
+
+        for batch in self.read_batches()? {
+            let expired = match older_than_days {
+                Some(days) => days_after(batch.created_at, days)? <= now,
+                None => batch.expires_at <= now,
+            };
+            if !expired {
+                continue;
+            }
+
+            fs::remove_dir_all(self.root.join(&batch.id))
+                .map_err(|e| format!("Failed to purge batch {}: {}", batch.id, e))?;
+            outcome.batches_purged += 1;
+            outcome.bytes_freed += batch
+                .items
+                .iter()
+                .filter(|i| !i.restored)
+                .map(|i| i.size_bytes)
+                .sum::<u64>();
+        }
+
+        Ok(outcome)
+    }
+
+    fn check_movable(&self, path: &Path) -> Result<fs::Metadata, String> {
+        let metadata =
+            fs::symlink_metadata(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        if !metadata.is_file() {
+            return Err("Only regular files can be quarantined".to_string());
+        }
+
+        let canonical =
+            fs::canonicalize(path).map_err(|e| format!("Failed to resolve path: {}", e))?;
+        let root = fs::canonicalize(&self.root).unwrap_or_else(|_| self.root.clone());
+        if canonical.starts_with(&root) {
+            return Err("File is already in quarantine".to_string());
+        }
+        Ok(metadata)
+    }
+
+    fn new_batch_id(&self, created_at: DateTime<Utc>) -> String {
+        let base = created_at.format("%Y%m%dT%H%M%S%3fZ").to_string();
+        let mut id = base.clone();
+        let mut suffix = 1;
+        while self.root.join(&id).exists() {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        id
+    }
+
+    fn batch_dir(&self, batch_id: &str) -> Result<PathBuf, String> {
+        // Ids are directory names; refuse anything that could escape the quarantine
+        let is_plain_name = Path::new(batch_id)
+            .file_name()
+            .is_some_and(|n| n == batch_id);
+        let dir = self.root.join(batch_id);
+        if !is_plain_name || !dir.join(MANIFEST_FILE_NAME).is_file() {
+            return Err(format!("Quarantine batch {} not found", batch_id));
+        }
+        Ok(dir)
+    }
+
+    fn read_batches(&self) -> Result<Vec<QuarantineBatch>, String> {
+        let entries = fs::read_dir(&self.root)
+            .map_err(|e| format!("Failed to read quarantine directory: {}", e))?;
+
+        let mut batches: Vec<QuarantineBatch> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path().join(MANIFEST_FILE_NAME))
+            .filter(|manifest| manifest.is_file())
+            .filter_map(|manifest| match read_json(&manifest) {
+                Ok(batch) => Some(batch),
+                Err(e) => {
+                    eprintln!("Skipping unreadable quarantine batch: {}", e);
+                    None
+                }
+            })
+            .collect();
+
+        batches.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(batches)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, QuarantineSettings>, String> {
+        self.settings
+            .lock()
+            .map_err(|_| "Quarantine is unavailable".to_string())
+    }
+}
+
+fn validate_retention_days(days: u32) -> Result<(), String> {
+    if !(1..=MAX_RETENTION_DAYS).contains(&days) {
+        return Err(format!(
+            "Retention must be between 1 and {} days, got {}",
+            MAX_RETENTION_DAYS, days
+        ));
+    }
+    Ok(())
+}
+
+fn days_after(time: DateTime<Utc>, days: u32) -> Result<DateTime<Utc>, String> {
+    time.checked_add_signed(Duration::days(days as i64))
+        .ok_or_else(|| format!("{} days after {} is out of range", days, time))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Writes through a temporary file so a crash never leaves half a manifest
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json)
+        .and_then(|_| fs::rename(&temp, path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn quarantine_files(
+    paths: Vec<String>,
+    reason: Option<String>,
+    quarantine: State<'_, Quarantine>,
+) -> Result<QuarantineOutcome, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.quarantine(&paths, reason))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn quarantine_duplicates(
+    groups: Vec<DuplicateGroup>,
+    quarantine: State<'_, Quarantine>,
+) -> Result<QuarantineOutcome, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.quarantine_duplicates(&groups))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn list_quarantine(
+    quarantine: State<'_, Quarantine>,
+) -> Result<Vec<QuarantineBatch>, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.list())
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn restore_quarantine_batch(
+    batch_id: String,
+    quarantine: State<'_, Quarantine>,
+) -> Result<RestoreOutcome, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.restore(&batch_id))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn purge_quarantine(
+    older_than_days: Option<u32>,
+    quarantine: State<'_, Quarantine>,
+) -> Result<PurgeOutcome, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.purge(older_than_days))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn set_quarantine_retention(
+    retention_days: u32,
+    quarantine: State<'_, Quarantine>,
+) -> Result<QuarantineSettings, String> {
+    let quarantine = quarantine.inner().clone();
+    tokio::task::spawn_blocking(move || quarantine.set_retention_days(retention_days))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarantined(dir: &Path) -> (Quarantine, Vec<String>) {
+        let quarantine = Quarantine::open(dir.join("quarantine")).unwrap();
+        let path = dir.join("old.log");
+        fs::write(&path, "log line").unwrap();
+        (quarantine, vec![path.to_string_lossy().to_string()])
+    }
+
+    #[test]
+    fn restores_a_quarantined_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let (quarantine, paths) = quarantined(dir.path());
+        let path = &paths[0];
+
+        let outcome = quarantine.quarantine(&paths, None).unwrap();
+        let batch = outcome.batch.unwrap();
+        assert!(!Path::new(path).exists());
+        assert_eq!(quarantine.list().unwrap().len(), 1);
+
+        let restored = quarantine.restore(&batch.id).unwrap();
+        assert_eq!(restored.restored, 1);
+        assert_eq!(fs::read_to_string(path).unwrap(), "log line");
+        assert!(quarantine.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restore_leaves_files_whose_path_was_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let (quarantine, paths) = quarantined(dir.path());
+        let path = &paths[0];
+
+        let batch = quarantine.quarantine(&paths, None).unwrap().batch.unwrap();
+        fs::write(path, "new log").unwrap();
+
+        let restored = quarantine.restore(&batch.id).unwrap();
+        assert_eq!(restored.restored, 0);
+        assert_eq!(restored.failures.len(), 1);
+        assert_eq!(fs::read_to_string(path).unwrap(), "new log");
+        assert_eq!(quarantine.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn purges_batches_for_good() {
+        let dir = tempfile::tempdir().unwrap();
+        let (quarantine, paths) = quarantined(dir.path());
+        let path = &paths[0];
+        quarantine.quarantine(&paths, None).unwrap();
+
+        assert_eq!(quarantine.purge(None).unwrap().batches_purged, 0);
+        let purged = quarantine.purge(Some(0)).unwrap();
+        assert_eq!(purged.batches_purged, 1);
+        assert_eq!(purged.bytes_freed, 8);
+        assert!(quarantine.list().unwrap().is_empty());
+        assert!(!Path::new(path).exists());
+    }
+}
@@ -0,0 +1,498 @@
+// src-tauri/src/cleanup/trash.rs
+use super::{move_file, redundant_copies, ActionFailure};
+use crate::scanners::local_scanner::DuplicateGroup;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
+
+pub const TRASH_LOG_FILE_NAME: &str = "trash_log.json";
+
+/// A file StackBurn moved to the desktop trash
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashedItem {
+    pub original_path: String,
+    pub trashed_path: String, // Inside the trash's files/ directory; identifies the item
+    pub info_path: String,    // The matching .trashinfo entry
+    pub size_bytes: u64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TrashOutcome {
+    pub trashed: Vec<TrashedItem>,
+    pub failures: Vec<ActionFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TrashRestoreOutcome {
+    pub restored: Vec<String>, // Original paths that are back in place
+    pub failures: Vec<ActionFailure>,
+}
+
+/// Moves files to the user's desktop trash following the freedesktop.org
+/// Trash specification, and remembers what it moved so it can be restored.
+/// Clones share one lock on the log.
+#[derive(Clone)]
+pub struct Trash {
+    log_path: PathBuf,
+    log: Arc<Mutex<()>>,
+}
+
+impl Trash {
+    pub fn open(log_path: PathBuf) -> Self {
+        Trash {
+            log_path,
+            log: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn trash(&self, paths: &[String]) -> Result<TrashOutcome, String> {
+        let _log = self.lock()?;
+        let mut items = self.read_log()?;
+        let mut outcome = TrashOutcome::default();
+
+        for path in paths {
+            match trash_path(Path::new(path)) {
+                Ok(item) => outcome.trashed.push(item),
+                Err(e) => outcome.failures.push(ActionFailure::new(path, e)),
+            }
+        }
+
+        items.extend(outcome.trashed.iter().cloned());
+        self.write_log(&items)?;
+        Ok(outcome)
+    }
+
+    /// Trashes every redundant copy in `groups`, keeping the first file of each
+    pub fn trash_duplicates(&self, groups: &[DuplicateGroup]) -> Result<TrashOutcome, String> {
+        self.trash(&redundant_copies(groups))
+    }
+
+    /// Items StackBurn trashed that are still in the trash, newest first.
+    /// Items emptied or restored from the file manager are forgotten.
+    pub fn list(&self) -> Result<Vec<TrashedItem>, String> {
+        let _log = self.lock()?;
+        let mut items = self.read_log()?;
+        let before = items.len();
+        items.retain(|item| fs::symlink_metadata(&item.trashed_path).is_ok());
+        if items.len() != before {
+            self.write_log(&items)?;
+        }
+
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(items)
+    }
+
+    /// Moves trashed items back to where they came from, refusing to
+    /// overwrite anything created there since
+    pub fn restore(&self, trashed_paths: &[String]) -> Result<TrashRestoreOutcome, String> {
+        let _log = self.lock()?;
+        let mut items = self.read_log()?;
+        let mut outcome = TrashRestoreOutcome::default();
+
+        for trashed in trashed_paths {
+            let Some(position) = items.iter().position(|i| &i.trashed_path == trashed) else {
+                outcome.failures.push(ActionFailure::new(
+                    trashed,
+                    "Not something StackBurn moved to the trash",
+                ));
+                continue;
+            };
+
+            let item = &items[position];
+            match restore_item(item) {
+                Ok(()) => {
+                    outcome.restored.push(item.original_path.clone());
+                    items.remove(position);
+                }
+                Err(e) => outcome.failures.push(ActionFailure::new(trashed, e)),
+            }
+        }
+
+        self.write_log(&items)?;
+        Ok(outcome)
+    }
+
+    fn read_log(&self) -> Result<Vec<TrashedItem>, String> {
+        match fs::read_to_string(&self.log_path) {
+            Ok(json) => {
+                serde_json::from_str(&json).map_err(|e| format!("Failed to parse trash log: {}", e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(format!("Failed to read trash log: {}", e)),
+        }
+    }
+
+    fn write_log(&self, items: &[TrashedItem]) -> Result<(), String> {
+        if let Some(parent) = self.log_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create trash log directory: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(items)
+            .map_err(|e| format!("Failed to serialize trash log: {}", e))?;
+        let temp = self.log_path.with_extension("json.tmp");
+        fs::write(&temp, json)
+            .and_then(|_| fs::rename(&temp, &self.log_path))
+            .map_err(|e| format!("Failed to write trash log: {}", e))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, String> {
+        self.log
+            .lock()
+            .map_err(|_| "Trash log is unavailable".to_string())
+    }
+}
+
+fn trash_path(path: &Path) -> Result<TrashedItem, String> {
+    let metadata = fs::symlink_metadata(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let original = absolute_path(path)?;
+    let trash_dir = xdg::trash_dir_for(&original)?;
+
+    let (name, info_path) = reserve_name(&trash_dir, &original)?;
+    let trashed_path = trash_dir.path.join("files").join(&name);
+    if let Err(e) = move_file(&original, &trashed_path) {
+        let _ = fs::remove_file(&info_path);
+        return Err(format!("Failed to move to trash: {}", e));
+    }
+
+    Ok(TrashedItem {
+        original_path: original.to_string_lossy().to_string(),
+        trashed_path: trashed_path.to_string_lossy().to_string(),
+        info_path: info_path.to_string_lossy().to_string(),
+        size_bytes: if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        },
+        deleted_at: Utc::now(),
+    })
+}
+
+fn restore_item(item: &TrashedItem) -> Result<(), String> {
+    let original = Path::new(&item.original_path);
+    if fs::symlink_metadata(original).is_ok() {
+        return Err("A file already exists at the original path".to_string());
+    }
+    if fs::symlink_metadata(&item.trashed_path).is_err() {
+        return Err("No longer in the trash".to_string());
+    }
+
+    move_file(Path::new(&item.trashed_path), original)
+        .map_err(|e| format!("Failed to restore: {}", e))?;
+    let _ = fs::remove_file(&item.info_path);
+    Ok(())
+}
+
+/// The trashed file's path with its parent resolved, so the trash entry
+/// points at the real location without following a final symlink
+fn absolute_path(path: &Path) -> Result<PathBuf, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("{} cannot be trashed", path.display()))?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = fs::canonicalize(parent).map_err(|e| format!("Failed to resolve path: {}", e))?;
+    Ok(parent.join(name))
+}
+
+/// A trash directory, and the top of the mount it belongs to unless it is
+/// the home trash
+pub(crate) struct TrashDir {
+    path: PathBuf,
+    topdir: Option<PathBuf>,
+}
+
+/// Claims a free name in the trash by creating its .trashinfo entry first,
+/// as the spec requires, so concurrent trashers never collide
+fn reserve_name(trash_dir: &TrashDir, original: &Path) -> Result<(String, PathBuf), String> {
+    let info_dir = trash_dir.path.join("info");
+    let file_name = original
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (file_name.as_str(), String::new()),
+    };
+
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(recorded_path(trash_dir, original)),
+        Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+
+    let mut attempt = 1;
+    loop {
+        let name = if attempt == 1 {
+            file_name.clone()
+        } else {
+            format!("{}.{}{}", stem, attempt, extension)
+        };
+        let info_path = info_dir.join(format!("{}.trashinfo", name));
+
+        match fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(mut file) => {
+                return file
+                    .write_all(info.as_bytes())
+                    .map(|_| (name, info_path.clone()))
+                    .map_err(|e| {
+                        let _ = fs::remove_file(&info_path);
+                        format!("Failed to write trash info: {}", e)
+                    });
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(format!("Failed to write trash info: {}", e)),
+        }
+    }
+}
+
+/// The path a .trashinfo entry records: relative to the mount for trashes
+/// at the top of one, as the spec asks, and absolute for the home trash
+fn recorded_path<'a>(trash_dir: &TrashDir, original: &'a Path) -> &'a Path {
+    trash_dir
+        .topdir
+        .as_deref()
+        .and_then(|topdir| original.strip_prefix(topdir).ok())
+        .unwrap_or(original)
+}
+
+/// Percent-encodes a path's raw bytes the way .trashinfo files expect
+fn encode_path(path: &Path) -> String {
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let lossy = path.to_string_lossy();
+    #[cfg(not(unix))]
+    let bytes = lossy.as_bytes();
+
+    let mut encoded = String::new();
+    for &byte in bytes {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod xdg {
+    use super::TrashDir;
+    use std::fs;
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+    use std::path::{Path, PathBuf};
+
+    const STICKY_BIT: u32 = 0o1000;
+
+    /// The trash a file should go to: the home trash when it lives on the
+    /// same filesystem, otherwise the trash at the top of its own mount
+    pub fn trash_dir_for(path: &Path) -> Result<TrashDir, String> {
+        let home_trash = TrashDir {
+            path: home_trash()?,
+            topdir: None,
+        };
+        let device = fs::symlink_metadata(path)
+            .map_err(|e| format!("Failed to read file: {}", e))?
+            .dev();
+
+        let home_device = fs::metadata(&home_trash.path).map(|m| m.dev());
+        if home_device.as_ref().is_ok_and(|dev| *dev == device) {
+            return Ok(home_trash);
+        }
+
+        // Fall back to the home trash, copying across filesystems, when the mount has none
+        Ok(topdir_trash(path, device).unwrap_or(home_trash))
+    }
+
+    fn home_trash() -> Result<PathBuf, String> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(dirs::data_dir)
+            .ok_or("No home directory for the trash")?;
+        let trash = data_home.join("Trash");
+        create_trash(&trash)?;
+        Ok(trash)
+    }
+
+    /// `$topdir/.Trash/$uid` if an administrator set up a shared trash,
+    /// otherwise `$topdir/.Trash-$uid`
+    fn topdir_trash(path: &Path, device: u64) -> Option<TrashDir> {
+        let topdir = mount_point(path, device)?;
+        // SAFETY: getuid has no preconditions and cannot fail
+        let uid = unsafe { libc::getuid() };
+
+        let shared = topdir.join(".Trash");
+        if let Ok(metadata) = fs::symlink_metadata(&shared) {
+            let usable = metadata.is_dir() && metadata.permissions().mode() & STICKY_BIT != 0;
+            if usable {
+                let trash = shared.join(uid.to_string());
+                if create_trash(&trash).is_ok() {
+                    return Some(TrashDir {
+                        path: trash,
+                        topdir: Some(topdir),
+                    });
+                }
+            }
+        }
+
+        let trash = topdir.join(format!(".Trash-{}", uid));
+        let is_symlink = fs::symlink_metadata(&trash).is_ok_and(|m| m.file_type().is_symlink());
+        (!is_symlink && create_trash(&trash).is_ok()).then_some(TrashDir {
+            path: trash,
+            topdir: Some(topdir),
+        })
+    }
+
+    /// The highest directory above `path` still on the same filesystem
+    fn mount_point(path: &Path, device: u64) -> Option<PathBuf> {
+        let mut topdir = path.parent()?.to_path_buf();
+        while let Some(parent) = topdir.parent() {
+            if fs::metadata(parent).ok()?.dev() != device {
+                break;
+            }
+            topdir = parent.to_path_buf();
+        }
+        Some(topdir)
+    }
+
+    fn create_trash(trash: &Path) -> Result<(), String> {
+        for dir in [trash.to_path_buf(), trash.join("files"), trash.join("info")] {
+            fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(&dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+mod xdg {
+    use super::TrashDir;
+    use std::path::Path;
+
+    pub fn trash_dir_for(_path: &Path) -> Result<TrashDir, String> {
+        Err("The desktop trash is only supported on Linux and BSD".to_string())
+    }
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn trash_files(
+    paths: Vec<String>,
+    trash: State<'_, Trash>,
+) -> Result<TrashOutcome, String> {
+    let trash = trash.inner().clone();
+    tokio::task::spawn_blocking(move || trash.trash(&paths))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn trash_duplicates(
+    groups: Vec<DuplicateGroup>,
+    trash: State<'_, Trash>,
+) -> Result<TrashOutcome, String> {
+    let trash = trash.inner().clone();
+    tokio::task::spawn_blocking(move || trash.trash_duplicates(&groups))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn list_trashed(trash: State<'_, Trash>) -> Result<Vec<TrashedItem>, String> {
+    let trash = trash.inner().clone();
+    tokio::task::spawn_blocking(move || trash.list())
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn restore_trashed(
+    trashed_paths: Vec<String>,
+    trash: State<'_, Trash>,
+) -> Result<TrashRestoreOutcome, String> {
+    let trash = trash.inner().clone();
+    tokio::task::spawn_blocking(move || trash.restore(&trashed_paths))
+        .await
+        .map_err(|e| format!("Cleanup worker failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn trashes_and_restores_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("XDG_DATA_HOME", dir.path().join("data"));
+        let trash = Trash::open(dir.path().join(TRASH_LOG_FILE_NAME));
+        let path = dir.path().join("draft.txt");
+        fs::write(&path, "draft").unwrap();
+
+        let outcome = trash.trash(&[path.to_string_lossy().to_string()]).unwrap();
+        assert!(outcome.failures.is_empty());
+        let item = &outcome.trashed[0];
+        assert!(!path.exists());
+        let info = fs::read_to_string(&item.info_path).unwrap();
+        let original = encode_path(Path::new(&item.original_path));
+        assert!(info.contains(&format!("Path={}\n", original)));
+        assert_eq!(trash.list().unwrap().len(), 1);
+
+        let restored = trash
+            .restore(std::slice::from_ref(&item.trashed_path))
+            .unwrap();
+        assert!(restored.failures.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "draft");
+        assert!(!Path::new(&item.info_path).exists());
+        assert!(trash.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn records_paths_relative_to_the_mount() {
+        let mount_trash = TrashDir {
+            path: PathBuf::from("/mnt/usb/.Trash-1000"),
+            topdir: Some(PathBuf::from("/mnt/usb")),
+        };
+        let home_trash = TrashDir {
+            path: PathBuf::from("/home/sam/.local/share/Trash"),
+            topdir: None,
+        };
+        let original = Path::new("/mnt/usb/photos/beach.jpg");
+
+        assert_eq!(
+            recorded_path(&mount_trash, original),
+            Path::new("photos/beach.jpg")
+        );
+        assert_eq!(recorded_path(&home_trash, original), original);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn encodes_raw_path_bytes() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/tmp/caf\xE9 menu.txt"));
+        assert_eq!(encode_path(path), "/tmp/caf%E9%20menu.txt");
+    }
+}
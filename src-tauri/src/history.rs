@@ -0,0 +1,333 @@
+// src-tauri/src/history.rs
+use crate::burn_score::BurnScoreResult;
+use crate::scanners::github_scanner::GitHubScanResult;
+use crate::scanners::local_scanner::FolderStats;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
+
+pub const HISTORY_FILE_NAME: &str = "history.sqlite3";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanKind {
+    Local,
+    #[serde(rename = "github")]
+    GitHub,
+    BurnScore,
+}
+
+impl ScanKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScanKind::Local => "local",
+            ScanKind::GitHub => "github",
+            ScanKind::BurnScore => "burn_score",
+        }
+    }
+}
+
+impl FromStr for ScanKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(ScanKind::Local),
+            "github" => Ok(ScanKind::GitHub),
+            "burn_score" => Ok(ScanKind::BurnScore),
+            _ => Err(format!("Unknown scan kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanSummary {
+    pub id: i64,
+    pub kind: ScanKind,
+    pub target: String, // Scanned path, or the sources a burn score covers
+    pub scanned_at: DateTime<Utc>,
+    pub total_items: i64,
+    pub total_bytes: i64, // Total size for scans, total bloat for burn scores
+    pub burn_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanRecord {
+    #[serde(flatten)]
+    pub summary: ScanSummary,
+    pub result: serde_json::Value, // The stored FolderStats, GitHubScanResult or BurnScoreResult
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanDelta {
+    pub from: ScanSummary,
+    pub to: ScanSummary,
+    pub elapsed_days: f64,
+    pub items_delta: i64,
+    pub size_delta_bytes: i64,
+    pub score_delta: Option<f64>,
+}
+
+/// Local store of every scan and burn score, used for trend tracking
+pub struct ScanHistory {
+    conn: Mutex<Connection>,
+}
+
+impl ScanHistory {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create history directory: {}", e))?;
+        }
+        let conn =
+            Connection::open(path).map_err(|e| format!("Failed to open scan history: {}", e))?;
+        Self::init(conn)
+    }
+
+    /// History that lives only as long as the process, used when the
+    /// on-disk database cannot be opened
+    pub fn open_in_memory() -> Result<Self, String> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open scan history: {}", e))?;
+        Self::init(conn)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS scans (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                target TEXT NOT NULL,
+                scanned_at TEXT NOT NULL,
+                total_items INTEGER NOT NULL,
+                total_bytes INTEGER NOT NULL,
+                burn_score REAL,
+                result TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS scans_by_kind ON scans (kind, scanned_at);",
+        )
+        .map_err(|e| format!("Failed to initialize scan history: {}", e))?;
+
+        Ok(ScanHistory {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record_local_scan(&self, path: &str, stats: &FolderStats) -> Result<i64, String> {
+        // Canonical, so ~/x and /home/u/x share one trend
+        let target = std::fs::canonicalize(path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string());
+        self.insert(
+            ScanKind::Local,
+            &target,
+            stats.scan_timestamp,
+            stats.total_files as i64,
+            stats.total_size as i64,
+            None,
+            stats,
+        )
+    }
+
+    pub fn record_github_scan(&self, result: &GitHubScanResult) -> Result<i64, String> {
+        self.insert(
+            ScanKind::GitHub,
+            "github",
+            result.scan_timestamp,
+            result.total_repos as i64,
+            result.total_size_kb * 1024,
+            None,
+            result,
+        )
+    }
+
+    pub fn record_burn_score(&self, result: &BurnScoreResult) -> Result<i64, String> {
+        let mut sources: Vec<&str> = result.category_scores.keys().map(|k| k.as_str()).collect();
+        sources.sort_unstable();
+
+        self.insert(
+            ScanKind::BurnScore,
+            &sources.join(", "),
+            result.calculated_at,
+            result.total_files_scanned as i64,
+            (result.total_bloat_size_gb * 1_073_741_824.0) as i64,
+            Some(result.overall_score),
+            result,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn insert<T: Serialize>(
+        &self,
+        kind: ScanKind,
+        target: &str,
+        scanned_at: DateTime<Utc>,
+        total_items: i64,
+        total_bytes: i64,
+        burn_score: Option<f64>,
+        result: &T,
+    ) -> Result<i64, String> {
+        let json = serde_json::to_string(result)
+            .map_err(|e| format!("Failed to serialize scan result: {}", e))?;
+        let conn = self.lock()?;
+
+        conn.execute(
+            "INSERT INTO scans (kind, target, scanned_at, total_items, total_bytes, burn_score, result)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                kind.as_str(),
+                target,
+                scanned_at.to_rfc3339_opts(SecondsFormat::Nanos, true),
+                total_items,
+                total_bytes,
+                burn_score,
+                json
+            ],
+        )
+        .map_err(|e| format!("Failed to record scan: {}", e))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Lists the most recent scans first, optionally only of one kind
+    pub fn list(&self, kind: Option<ScanKind>, limit: usize) -> Result<Vec<ScanSummary>, String> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, kind, target, scanned_at, total_items, total_bytes, burn_score
+                 FROM scans
+                 WHERE ?1 IS NULL OR kind = ?1
+                 ORDER BY scanned_at DESC, id DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to query scan history: {}", e))?;
+
+        let rows = stmt
+            .query_map(
+                params![kind.map(|k| k.as_str()), limit as i64],
+                summary_from_row,
+            )
+            .map_err(|e| format!("Failed to query scan history: {}", e))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read scan history: {}", e))
+    }
+
+    pub fn get(&self, id: i64) -> Result<ScanRecord, String> {
+        let conn = self.lock()?;
+        let record = conn
+            .query_row(
+                "SELECT id, kind, target, scanned_at, total_items, total_bytes, burn_score, result
+                 FROM scans WHERE id = ?1",
+                params![id],
+                |row| {
+                    let json: String = row.get(7)?;
+                    Ok((summary_from_row(row)?, json))
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read scan {}: {}", id, e))?;
+
+        let (summary, json) = record.ok_or_else(|| format!("Scan {} not found", id))?;
+        let result = serde_json::from_str(&json)
+            .map_err(|e| format!("Scan {} has a corrupt result: {}", id, e))?;
+
+        Ok(ScanRecord { summary, result })
+    }
+
+    /// Computes how much bloat changed between two scans of the same kind and target
+    pub fn compare(&self, from_id: i64, to_id: i64) -> Result<ScanDelta, String> {
+        let from = self.get(from_id)?.summary;
+        let to = self.get(to_id)?.summary;
+
+        if from.kind != to.kind {
+            return Err(format!(
+                "Cannot compare a {} scan with a {} scan",
+                from.kind.as_str(),
+                to.kind.as_str()
+            ));
+        }
+        if from.target != to.target {
+            return Err(format!(
+                "Cannot compare a scan of {} with a scan of {}",
+                from.target, to.target
+            ));
+        }
+
+        let score_delta = match (from.burn_score, to.burn_score) {
+            (Some(a), Some(b)) => Some(b - a),
+            _ => None,
+        };
+
+        Ok(ScanDelta {
+            elapsed_days: (to.scanned_at - from.scanned_at).num_seconds() as f64 / 86_400.0,
+            items_delta: to.total_items - from.total_items,
+            size_delta_bytes: to.total_bytes - from.total_bytes,
+            score_delta,
+            from,
+            to,
+        })
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.conn
+            .lock()
+            .map_err(|_| "Scan history is unavailable".to_string())
+    }
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn list_scans(
+    kind: Option<ScanKind>,
+    limit: Option<usize>,
+    history: State<'_, ScanHistory>,
+) -> Result<Vec<ScanSummary>, String> {
+    history.list(kind, limit.unwrap_or(50))
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn get_scan(id: i64, history: State<'_, ScanHistory>) -> Result<ScanRecord, String> {
+    history.get(id)
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn compare_scans(
+    from_id: i64,
+    to_id: i64,
+    history: State<'_, ScanHistory>,
+) -> Result<ScanDelta, String> {
+    history.compare(from_id, to_id)
+}
+
+fn summary_from_row(row: &Row) -> rusqlite::Result<ScanSummary> {
+    let kind: String = row.get(1)?;
+    let scanned_at: String = row.get(3)?;
+
+    Ok(ScanSummary {
+        id: row.get(0)?,
+        kind: kind.parse().map_err(|e: String| {
+            rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, e.into())
+        })?,
+        target: row.get(2)?,
+        scanned_at: DateTime::parse_from_rfc3339(&scanned_at)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    3,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
+        total_items: row.get(4)?,
+        total_bytes: row.get(5)?,
+        burn_score: row.get(6)?,
+    })
+}
@@ -0,0 +1,303 @@
+// src-tauri/src/lib.rs
+pub mod burn_score;
+pub mod cleanup;
+pub mod history;
+pub mod scanners;
+
+#[cfg(feature = "desktop")]
+use cleanup::quarantine::{self, Quarantine};
+#[cfg(feature = "desktop")]
+use cleanup::trash::{self, Trash};
+#[cfg(feature = "desktop")]
+use cleanup::{dedupe, empty_dirs, plan};
+#[cfg(feature = "desktop")]
+use history::ScanHistory;
+#[cfg(feature = "desktop")]
+use scanners::archives;
+#[cfg(feature = "desktop")]
+use scanners::drive_scanner::{self, DriveState};
+#[cfg(feature = "desktop")]
+use scanners::file_index::FileIndex;
+#[cfg(feature = "desktop")]
+use scanners::github_scanner::{self, GitHubState};
+#[cfg(feature = "desktop")]
+use scanners::local_scanner;
+#[cfg(feature = "desktop")]
+use scanners::progress::{self, ActiveScans};
+#[cfg(feature = "desktop")]
+use scanners::size_tree::{self, SizeTrees};
+use std::path::PathBuf;
+#[cfg(feature = "desktop")]
+use tauri::Manager;
+
+// Matches the identifier in tauri.conf.json, so the CLI shares the app's data
+const APP_IDENTIFIER: &str = "com.stackburn.desktop";
+
+/// The desktop app's data directory, if the platform has one
+pub fn default_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+#[cfg(feature = "desktop")]
+fn init_plugins() -> tauri::Builder<tauri::Wry> {
+    println!("Initializing Tauri plugins...");
+    let mut builder = tauri::Builder::default();
+
+    // Initialize shell plugin
+    match std::panic::catch_unwind(|| tauri_plugin_shell::init()) {
+        Ok(plugin) => {
+            println!("✓ Shell plugin initialized successfully");
+            builder = builder.plugin(plugin);
+        }
+        Err(e) => {
+            eprintln!("✗ Shell plugin failed to initialize: {:?}", e);
+            eprintln!("  App will continue without shell functionality");
+        }
+    }
+
+    // Initialize fs plugin
+    match std::panic::catch_unwind(|| tauri_plugin_fs::init()) {
+        Ok(plugin) => {
+            println!("✓ File system plugin initialized successfully");
+            builder = builder.plugin(plugin);
+        }
+        Err(e) => {
+            eprintln!("✗ File system plugin failed to initialize: {:?}", e);
+            eprintln!("  App will continue with limited file access");
+        }
+    }
+
+    // Initialize dialog plugin
+    match std::panic::catch_unwind(|| tauri_plugin_dialog::init()) {
+        Ok(plugin) => {
+            println!("✓ Dialog plugin initialized successfully");
+            builder = builder.plugin(plugin);
+        }
+        Err(e) => {
+            eprintln!("✗ Dialog plugin failed to initialize: {:?}", e);
+            eprintln!("  App will continue without native dialogs");
+        }
+    }
+
+    // Initialize http plugin
+    match std::panic::catch_unwind(|| tauri_plugin_http::init()) {
+        Ok(plugin) => {
+            println!("✓ HTTP plugin initialized successfully");
+            builder = builder.plugin(plugin);
+        }
+        Err(e) => {
+            eprintln!("✗ HTTP plugin failed to initialize: {:?}", e);
+            eprintln!("  App will continue without HTTP client functionality");
+        }
+    }
+
+    println!("Plugin initialization complete");
+    builder
+}
+
+/// Opens a store under the app data directory, falling back to one in memory
+/// so the app still starts.
+#[cfg(feature = "desktop")]
+fn open_store<T>(
+    name: &str,
+    path: Option<PathBuf>,
+    open: fn(&std::path::Path) -> Result<T, String>,
+    open_in_memory: fn() -> Result<T, String>,
+) -> Result<T, String> {
+    let opened = match path {
+        Some(path) => open(&path),
+        None => Err("no app data directory".to_string()),
+    };
+
+    opened.or_else(|e| {
+        eprintln!("✗ {} unavailable: {}", name, e);
+        eprintln!("  Data from this session will not be kept");
+        open_in_memory()
+    })
+}
+
+/// Builds and runs the desktop app. Exits the process if Tauri fails to start.
+#[cfg(feature = "desktop")]
+pub fn run() {
+    println!("=== TAURI MINIMAL TEST ===");
+
+    init_plugins()
+        .manage(GitHubState::default())
+        .manage(DriveState::default())
+        .manage(ActiveScans::default())
+        .manage(SizeTrees::default())
+        .invoke_handler(tauri::generate_handler![
+            // Google Drive
+            drive_scanner::init_oauth_flow,
+            drive_scanner::handle_oauth_callback,
+            drive_scanner::scan_drive,
+            drive_scanner::get_drive_metadata,
+            // Local folders
+            local_scanner::scan_directory,
+            local_scanner::get_folder_stats,
+            local_scanner::detect_duplicates,
+            local_scanner::scan_build_artifacts,
+            archives::list_archive_members,
+            size_tree::get_subtree,
+            // GitHub
+            github_scanner::authenticate_github,
+            github_scanner::scan_repositories,
+            github_scanner::get_stale_repos,
+            // Scan progress
+            progress::cancel_scan,
+            // Burn score engine
+            burn_score::calculate_burn_score,
+            burn_score::get_file_categories,
+            burn_score::generate_report,
+            // Scan history
+            history::list_scans,
+            history::get_scan,
+            history::compare_scans,
+            // Cleanup
+            quarantine::quarantine_files,
+            quarantine::quarantine_duplicates,
+            quarantine::list_quarantine,
+            quarantine::restore_quarantine_batch,
+            quarantine::purge_quarantine,
+            quarantine::set_quarantine_retention,
+            plan::create_cleanup_plan,
+            plan::save_cleanup_plan,
+            plan::load_cleanup_plan,
+            plan::apply_cleanup_plan,
+            dedupe::dedupe_duplicates,
+            empty_dirs::remove_empty_dirs,
+            trash::trash_files,
+            trash::trash_duplicates,
+            trash::list_trashed,
+            trash::restore_trashed,
+        ])
+        .setup(|app| {
+            println!("=== TAURI V2 DEBUG SETUP ===");
+
+            // Safely get current directory
+            match std::env::current_dir() {
+                Ok(dir) => println!("Current directory: {:?}", dir),
+                Err(e) => eprintln!("Failed to get current directory: {}", e),
+            }
+
+            // Safely get app data directory and open the stores kept there
+            let data_dir = match app.path().app_data_dir() {
+                Ok(dir) => {
+                    println!("App data directory: {:?}", dir);
+                    Some(dir)
+                }
+                Err(e) => {
+                    eprintln!("Failed to get app data directory: {}", e);
+                    None
+                }
+            };
+            app.manage(open_store(
+                "Scan history",
+                data_dir
+                    .as_ref()
+                    .map(|dir| dir.join(history::HISTORY_FILE_NAME)),
+                ScanHistory::open,
+                ScanHistory::open_in_memory,
+            )?);
+            app.manage(open_store(
+                "File index",
+                data_dir
+                    .as_ref()
+                    .map(|dir| dir.join(scanners::file_index::FILE_INDEX_FILE_NAME)),
+                FileIndex::open,
+                FileIndex::open_in_memory,
+            )?);
+
+            // Quarantined files must survive restarts, so fall back to a temp directory rather than memory
+            let quarantine_root = data_dir
+                .clone()
+                .unwrap_or_else(std::env::temp_dir)
+                .join(quarantine::QUARANTINE_DIR_NAME);
+            let quarantine = Quarantine::open(quarantine_root)?;
+            match quarantine.purge(None) {
+                Ok(purged) if purged.batches_purged > 0 => println!(
+                    "✓ Purged {} expired quarantine batches ({} bytes)",
+                    purged.batches_purged, purged.bytes_freed
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!("✗ Failed to purge expired quarantine batches: {}", e),
+            }
+            app.manage(quarantine);
+            app.manage(Trash::open(
+                data_dir
+                    .unwrap_or_else(std::env::temp_dir)
+                    .join(trash::TRASH_LOG_FILE_NAME),
+            ));
+
+            // Check if dist files exist and can be read
+            let dist_path = std::path::Path::new("../dist");
+            println!("Dist directory exists: {}", dist_path.exists());
+            if dist_path.exists() {
+                match std::fs::read_dir(dist_path) {
+                    Ok(entries) => {
+                        let files: Result<Vec<_>, _> = entries.collect();
+                        match files {
+                            Ok(files) => {
+                                let names: Vec<_> = files.iter().map(|e| e.file_name()).collect();
+                                println!("Dist contents: {:?}", names);
+                            }
+                            Err(e) => eprintln!("Failed to read dist directory contents: {}", e),
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to open dist directory: {}", e),
+                }
+
+                // Check if index.html can be read
+                let index_path = dist_path.join("index.html");
+                if index_path.exists() {
+                    println!("index.html exists: {}", index_path.display());
+                    match std::fs::read_to_string(&index_path) {
+                        Ok(content) => {
+                            println!("index.html content length: {} chars", content.len())
+                        }
+                        Err(e) => eprintln!("Failed to read index.html: {}", e),
+                    }
+                } else {
+                    eprintln!("WARNING: index.html not found at {:?}", index_path);
+                }
+            } else {
+                eprintln!("WARNING: Dist directory not found at {:?}", dist_path);
+                eprintln!("  Make sure to run 'npm run build' before starting the Tauri app");
+            }
+
+            // Try to get the window and enable dev tools
+            match app.get_webview_window("main") {
+                Some(window) => {
+                    println!("Window found: {:?}", window.label());
+                    match window.url() {
+                        Ok(url) => println!("Window URL: {:?}", url),
+                        Err(e) => eprintln!("Failed to get window URL: {}", e),
+                    }
+
+                    #[cfg(debug_assertions)]
+                    {
+                        println!("Opening dev tools...");
+                        window.open_devtools();
+                    }
+                }
+                None => {
+                    eprintln!("WARNING: No main window found!");
+                    eprintln!("  This may indicate a frontend loading issue");
+                }
+            }
+
+            println!("=== TAURI V2 DEBUG SETUP COMPLETE ===");
+            println!("Setup complete - letting Tauri handle frontend loading");
+
+            Ok(())
+        })
+        .run(tauri::generate_context!())
+        .unwrap_or_else(|e| {
+            eprintln!("✗ CRITICAL: Tauri application failed to start: {:?}", e);
+            eprintln!("  This is likely due to:");
+            eprintln!("  - Missing or corrupted frontend files");
+            eprintln!("  - Plugin initialization failures");
+            eprintln!("  - System compatibility issues");
+            std::process::exit(1);
+        });
+}
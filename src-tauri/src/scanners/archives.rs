@@ -0,0 +1,392 @@
+// src-tauri/src/scanners/archives.rs
+use super::duplicates::calculate_file_hash;
+use super::local_scanner::LocalFile;
+use super::progress::{ScanMonitor, ScanPhase};
+use super::workers::WorkerPool;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "desktop")]
+use tauri::command;
+
+// Unpacked files of an archive that must be found together before it counts as extracted
+const MIN_EXTRACTED_SHARE: f64 = 0.9;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarZst,
+    Gz, // A single gzipped file
+}
+
+impl ArchiveFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Some(ArchiveFormat::TarZst)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".gz") {
+            Some(ArchiveFormat::Gz)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveMember {
+    pub path: String, // Relative to the archive root, always with `/`
+    pub size: u64,
+    pub hash: String, // SHA-256, comparable with LocalFile::hash
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveListing {
+    pub path: String,
+    pub format: ArchiveFormat,
+    pub unpacked_size: u64,
+    pub members: Vec<ArchiveMember>, // Regular files only
+}
+
+/// A folder on disk holding an archive's files, unchanged
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Extraction {
+    pub folder: String,
+    pub matched_files: usize,
+    pub matched_size: u64,
+    pub complete: bool,  // Every file of the archive is there
+    pub exclusive: bool, // The folder holds nothing but the archive's files
+}
+
+/// An archive whose contents were found unpacked elsewhere in the scan
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveReport {
+    pub path: String,
+    pub format: ArchiveFormat,
+    pub size: u64,
+    pub member_count: usize,
+    pub unpacked_size: u64,
+    pub extractions: Vec<Extraction>,
+    pub redundant: Vec<String>, // The archive or repeated extractions that can go
+    pub redundant_size: u64,
+}
+
+/// Lists every archive among `files`, hashes its members and looks for the
+/// same files unpacked elsewhere in `files`. When a complete extraction
+/// exists the archive is redundant, and so is every further complete
+/// extraction whose folder holds nothing else. Runs on `workers` threads and
+/// stops early if `monitor` is cancelled.
+pub fn find_extracted_archives(
+    files: &mut [LocalFile],
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> Vec<ArchiveReport> {
+    let pool = WorkerPool::new(workers, "archive");
+    monitor.set_phase(ScanPhase::Hashing);
+
+    let archives: Vec<usize> = (0..files.len())
+        .filter(|&i| ArchiveFormat::from_path(Path::new(&files[i].path)).is_some())
+        .collect();
+    monitor.will_hash(archives.iter().map(|&i| files[i].size).sum());
+
+    let shared: &[LocalFile] = files;
+    let listings: Vec<(usize, ArchiveListing)> = pool.filter_map(&archives, |&i| {
+        if monitor.is_cancelled() {
+            return None;
+        }
+        let path = Path::new(&shared[i].path);
+        let listing = list_archive(path);
+        monitor.bytes_hashed(shared[i].size, path);
+        listing.ok().map(|listing| (i, listing))
+    });
+
+    // Only files the size of some member can hold its contents
+    let member_sizes: HashSet<u64> = listings
+        .iter()
+        .flat_map(|(_, listing)| listing.members.iter().map(|m| m.size))
+        .collect();
+    let unhashed: Vec<usize> = (0..files.len())
+        .filter(|&i| files[i].hash.is_none() && member_sizes.contains(&files[i].size))
+        .collect();
+    let shared: &[LocalFile] = files;
+    monitor.will_hash(unhashed.iter().map(|&i| shared[i].size).sum());
+    let hashes: Vec<(usize, String)> = pool.filter_map(&unhashed, |&i| {
+        if monitor.is_cancelled() {
+            return None;
+        }
+        let path = Path::new(&shared[i].path);
+        let hash = calculate_file_hash(path).ok();
+        monitor.bytes_hashed(shared[i].size, path);
+        hash.map(|hash| (i, hash))
+    });
+    for (i, hash) in hashes {
+        files[i].hash = Some(hash);
+    }
+    if monitor.is_cancelled() {
+        return Vec::new();
+    }
+
+    let mut by_hash: HashMap<&str, Vec<&str>> = HashMap::new();
+    for file in files.iter() {
+        if let Some(hash) = &file.hash {
+            by_hash.entry(hash).or_default().push(&file.path);
+        }
+    }
+    let mut reports: Vec<ArchiveReport> = listings
+        .iter()
+        .filter_map(|(i, listing)| match_extractions(&files[*i], listing, &by_hash))
+        .collect();
+
+    reports.sort_by_key(|r| std::cmp::Reverse(r.redundant_size));
+    reports
+}
+
+/// Finds folders holding at least `MIN_EXTRACTED_SHARE` of the archive's files
+fn match_extractions(
+    archive: &LocalFile,
+    listing: &ArchiveListing,
+    by_hash: &HashMap<&str, Vec<&str>>,
+) -> Option<ArchiveReport> {
+    if listing.members.is_empty() {
+        return None;
+    }
+    // Tarballs usually wrap everything in one top folder; count that folder as the extraction
+    let prefix = common_folder(&listing.members);
+
+    let mut folders: HashMap<PathBuf, (HashSet<&Path>, u64)> = HashMap::new();
+    for member in &listing.members {
+        let relative = &member.path[prefix.len()..];
+        for found in by_hash.get(member.hash.as_str()).into_iter().flatten() {
+            if *found == archive.path {
+                continue;
+            }
+            if let Some(folder) = strip_relative(Path::new(found), relative) {
+                let entry = folders.entry(folder).or_default();
+                entry.0.insert(Path::new(found));
+                entry.1 += member.size;
+            }
+        }
+    }
+
+    let needed = (listing.members.len() as f64 * MIN_EXTRACTED_SHARE).ceil() as usize;
+    let mut extractions: Vec<Extraction> = folders
+        .into_iter()
+        .filter(|(_, (matched, _))| matched.len() >= needed)
+        .map(|(folder, (matched, matched_size))| Extraction {
+            folder: folder.to_string_lossy().to_string(),
+            matched_files: matched.len(),
+            matched_size,
+            complete: matched.len() == listing.members.len(),
+            exclusive: holds_only(&folder, &matched),
+        })
+        .collect();
+    if extractions.is_empty() {
+        return None;
+    }
+    // Folders next to the archive first, so those are the extractions kept
+    let archive_dir = Path::new(&archive.path).parent();
+    extractions.sort_by_key(|e| {
+        (
+            archive_dir.is_none_or(|dir| Path::new(&e.folder).parent() != Some(dir)),
+            e.folder.clone(),
+        )
+    });
+
+    let mut redundant = Vec::new();
+    let mut redundant_size = 0;
+    let mut complete = extractions.iter().filter(|e| e.complete);
+    if complete.next().is_some() {
+        redundant.push(archive.path.clone());
+        redundant_size += archive.size;
+        for extra in complete.filter(|e| e.exclusive) {
+            redundant.push(extra.folder.clone());
+            redundant_size += extra.matched_size;
+        }
+    }
+
+    Some(ArchiveReport {
+        path: archive.path.clone(),
+        format: listing.format,
+        size: archive.size,
+        member_count: listing.members.len(),
+        unpacked_size: listing.unpacked_size,
+        extractions,
+        redundant,
+        redundant_size,
+    })
+}
+
+/// Whether everything on disk under `dir` is one of `files`. Files the scan
+/// skipped, like dotfiles or excluded folders, count too; so does anything
+/// that can't be read.
+fn holds_only(dir: &Path, files: &HashSet<&Path>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    for entry in entries {
+        let Ok(entry) = entry else {
+            return false;
+        };
+        let path = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        let held = if is_dir {
+            holds_only(&path, files)
+        } else {
+            files.contains(path.as_path())
+        };
+        if !held {
+            return false;
+        }
+    }
+    true
+}
+
+/// The folder every member sits in, e.g. "project-1.0/", or "" if none
+fn common_folder(members: &[ArchiveMember]) -> String {
+    let first = match members[0].path.split_once('/') {
+        Some((folder, _)) => folder,
+        None => return String::new(),
+    };
+    let prefix = format!("{}/", first);
+    if members.iter().all(|m| m.path.starts_with(&prefix)) {
+        prefix
+    } else {
+        String::new()
+    }
+}
+
+/// `path` with `relative` removed from its end, or None if it doesn't end with it
+fn strip_relative(path: &Path, relative: &str) -> Option<PathBuf> {
+    let mut folder = path;
+    for part in relative.rsplit('/') {
+        if folder.file_name()?.to_str()? != part {
+            return None;
+        }
+        folder = folder.parent()?;
+    }
+    Some(folder.to_path_buf())
+}
+
+/// Reads every regular file in the archive at `path` and hashes it
+pub fn list_archive(path: &Path) -> Result<ArchiveListing, String> {
+    let format = ArchiveFormat::from_path(path)
+        .ok_or_else(|| format!("{} is not a supported archive", path.display()))?;
+    let file =
+        fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let members = match format {
+        ArchiveFormat::Zip => zip_members(file),
+        ArchiveFormat::Tar => tar_members(file),
+        ArchiveFormat::TarGz => tar_members(GzDecoder::new(file)),
+        ArchiveFormat::TarZst => zstd::Decoder::new(file).and_then(tar_members),
+        ArchiveFormat::Gz => {
+            let name = path
+                .file_stem()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            hash_reader(GzDecoder::new(file)).map(|(size, hash)| {
+                vec![ArchiveMember {
+                    path: name,
+                    size,
+                    hash,
+                }]
+            })
+        }
+    }
+    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    Ok(ArchiveListing {
+        path: path.to_string_lossy().to_string(),
+        format,
+        unpacked_size: members.iter().map(|m| m.size).sum(),
+        members,
+    })
+}
+
+fn zip_members(file: fs::File) -> io::Result<Vec<ArchiveMember>> {
+    let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+    let mut members = Vec::new();
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(io::Error::other)?;
+        if !entry.is_file() {
+            continue;
+        }
+        // Entries escaping the archive root can't be matched to an extraction
+        let Some(path) = entry.enclosed_name().and_then(|p| member_path(&p)) else {
+            continue;
+        };
+        let (size, hash) = hash_reader(entry)?;
+        members.push(ArchiveMember { path, size, hash });
+    }
+
+    Ok(members)
+}
+
+fn tar_members(reader: impl Read) -> io::Result<Vec<ArchiveMember>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut members = Vec::new();
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = member_path(&entry.path()?) else {
+            continue;
+        };
+        let (size, hash) = hash_reader(entry)?;
+        members.push(ArchiveMember { path, size, hash });
+    }
+
+    Ok(members)
+}
+
+/// Normalizes a member path to `a/b/c`, dropping `./` and rejecting `..` and absolute paths
+fn member_path(path: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn hash_reader(mut reader: impl Read) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 8192];
+    let mut size = 0u64;
+
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        size += bytes_read as u64;
+    }
+
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+#[cfg_attr(feature = "desktop", command)]
+pub async fn list_archive_members(path: String) -> Result<ArchiveListing, String> {
+    tokio::task::spawn_blocking(move || list_archive(Path::new(&path)))
+        .await
+        .map_err(|e| format!("Archive worker failed: {}", e))?
+}
@@ -0,0 +1,279 @@
+// src-tauri/src/scanners/build_artifacts.rs
+use super::local_scanner::{allocated_size, file_id};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::scan_errors::{ErrorCounts, ScanError, MAX_LISTED_ERRORS};
+use super::workers::WorkerPool;
+use chrono::{DateTime, Utc};
+use ignore::{WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+
+// Output directories and the manifests that prove a project can rebuild them
+const ARTIFACT_DIRECTORIES: &[(&str, &[&str])] = &[
+    ("node_modules", &["package.json"]),
+    ("target", &["Cargo.toml", "pom.xml"]),
+    (
+        "build",
+        &[
+            "build.gradle",
+            "build.gradle.kts",
+            "CMakeLists.txt",
+            "package.json",
+            "pyproject.toml",
+            "setup.py",
+        ],
+    ),
+    ("dist", &["package.json", "pyproject.toml", "setup.py"]),
+];
+
+const PROJECT_TYPES: &[(&str, &str)] = &[
+    ("package.json", "Node.js"),
+    ("Cargo.toml", "Rust"),
+    ("pom.xml", "Maven"),
+    ("build.gradle", "Gradle"),
+    ("build.gradle.kts", "Gradle"),
+    ("CMakeLists.txt", "CMake"),
+    ("pyproject.toml", "Python"),
+    ("setup.py", "Python"),
+];
+
+// Project files looked at when working out when a project was last touched
+const PROJECT_TOUCH_LIMIT: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildArtifact {
+    pub path: String,
+    pub kind: String,    // Directory name, e.g. "node_modules"
+    pub size_bytes: u64, // Each file counted once, however many hard links lead to it
+    #[serde(default)]
+    pub disk_usage_bytes: u64, // Allocated bytes, so sparse files count only what they store
+    pub file_count: usize,
+    pub project_root: Option<String>,
+    pub project_type: Option<String>, // e.g. "Rust", from the manifest that owns the artifact
+    pub project_last_modified: Option<DateTime<Utc>>, // Newest source file, ignoring artifacts
+    // Owned by a project that can rebuild it, so safe to delete
+    pub regenerable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactScanResult {
+    pub root: String,
+    pub artifacts: Vec<BuildArtifact>, // Largest first
+    pub total_size_bytes: u64,
+    #[serde(default)]
+    pub disk_usage_bytes: u64,
+    pub regenerable_size_bytes: u64,
+    pub scan_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub incomplete: bool,
+    #[serde(default)]
+    pub errors: Vec<ScanError>, // Paths that could not be read, the first MAX_LISTED_ERRORS of them
+    #[serde(default)]
+    pub error_counts: ErrorCounts,
+}
+
+/// What `directory_size` found below one artifact directory
+#[derive(Default)]
+struct DirectorySize {
+    size: u64,
+    disk_usage: u64,
+    file_count: usize,
+    errors: Vec<ScanError>,
+}
+
+/// Finds dependency and build output directories under `root`, sizes them
+/// and works out which project owns each one. Artifacts are not descended
+/// into, so nested `node_modules` count towards their outermost directory.
+/// A file hard-linked into several artifacts, as pnpm does, counts towards
+/// the first one sized.
+pub fn find_build_artifacts(
+    root: &Path,
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> ArtifactScanResult {
+    monitor.set_phase(ScanPhase::Walking);
+    let (tx, rx) = mpsc::channel();
+
+    WalkBuilder::new(root)
+        .standard_filters(false)
+        .hidden(true)
+        .threads(workers)
+        .build_parallel()
+        .run(|| {
+            let tx = tx.clone();
+            Box::new(move |entry| {
+                if monitor.is_cancelled() {
+                    return WalkState::Quit;
+                }
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        if let Some(error) = ScanError::from_walk(&e) {
+                            let _ = tx.send(Err(error));
+                        }
+                        return WalkState::Continue;
+                    }
+                };
+                if !entry.file_type().is_some_and(|t| t.is_dir()) || entry.depth() == 0 {
+                    return WalkState::Continue;
+                }
+
+                match artifact_markers(entry.path()) {
+                    Some(markers) => {
+                        let _ = tx.send(Ok((entry.into_path(), markers)));
+                        WalkState::Skip
+                    }
+                    None => WalkState::Continue,
+                }
+            })
+        });
+    drop(tx);
+    let mut found: Vec<(PathBuf, &[&str])> = Vec::new();
+    let mut errors = Vec::new();
+    for walked in rx {
+        match walked {
+            Ok(artifact) => found.push(artifact),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    monitor.set_phase(ScanPhase::Analyzing);
+    let linked = Mutex::new(HashSet::new());
+    let sizes = WorkerPool::new(workers, "artifact")
+        .map(&found, |(path, _)| directory_size(path, &linked, monitor));
+
+    let mut last_touched: HashMap<PathBuf, Option<DateTime<Utc>>> = HashMap::new();
+    let mut artifacts: Vec<BuildArtifact> = found
+        .iter()
+        .zip(sizes)
+        .map(|((path, markers), size)| {
+            errors.extend(size.errors);
+            let project = path.parent().and_then(|parent| {
+                let marker = markers.iter().find(|m| parent.join(m).is_file())?;
+                Some((parent, *marker))
+            });
+            let project_last_modified = project.and_then(|(parent, _)| {
+                *last_touched
+                    .entry(parent.to_path_buf())
+                    .or_insert_with(|| project_last_modified(parent))
+            });
+
+            BuildArtifact {
+                path: path.to_string_lossy().to_string(),
+                kind: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                size_bytes: size.size,
+                disk_usage_bytes: size.disk_usage,
+                file_count: size.file_count,
+                project_root: project.map(|(parent, _)| parent.to_string_lossy().to_string()),
+                project_type: project.and_then(|(_, marker)| project_type(marker)),
+                project_last_modified,
+                regenerable: project.is_some(),
+            }
+        })
+        .collect();
+    artifacts.sort_by_key(|a| std::cmp::Reverse(a.size_bytes));
+    let error_counts = ErrorCounts::tally(&errors);
+    errors.truncate(MAX_LISTED_ERRORS);
+
+    ArtifactScanResult {
+        root: root.to_string_lossy().to_string(),
+        total_size_bytes: artifacts.iter().map(|a| a.size_bytes).sum(),
+        disk_usage_bytes: artifacts.iter().map(|a| a.disk_usage_bytes).sum(),
+        regenerable_size_bytes: artifacts
+            .iter()
+            .filter(|a| a.regenerable)
+            .map(|a| a.size_bytes)
+            .sum(),
+        artifacts,
+        scan_timestamp: Utc::now(),
+        incomplete: monitor.is_cancelled(),
+        errors,
+        error_counts,
+    }
+}
+
+/// The manifests that would own `path` if it is an artifact directory
+fn artifact_markers(path: &Path) -> Option<&'static [&'static str]> {
+    let name = path.file_name()?.to_str()?;
+    ARTIFACT_DIRECTORIES
+        .iter()
+        .find(|(dir, _)| *dir == name)
+        .map(|(_, markers)| *markers)
+}
+
+fn project_type(marker: &str) -> Option<String> {
+    PROJECT_TYPES
+        .iter()
+        .find(|(file, _)| *file == marker)
+        .map(|(_, kind)| kind.to_string())
+}
+
+/// Total size and file count of everything under `path`. Files with several
+/// hard links are only counted if no other artifact claimed them in `linked`.
+fn directory_size(
+    path: &Path,
+    linked: &Mutex<HashSet<(u64, u64)>>,
+    monitor: &ScanMonitor,
+) -> DirectorySize {
+    let mut size = DirectorySize::default();
+
+    for entry in WalkBuilder::new(path).standard_filters(false).build() {
+        if monitor.is_cancelled() {
+            break;
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                size.errors.extend(ScanError::from_walk(&e));
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                size.errors.extend(ScanError::from_walk(&e));
+                continue;
+            }
+        };
+        monitor.file_seen(entry.path());
+
+        if let Some(id) = file_id(&metadata).filter(|id| id.links > 1) {
+            let first = linked
+                .lock()
+                .map(|mut seen| seen.insert((id.device, id.inode)))
+                .unwrap_or(true);
+            if !first {
+                continue;
+            }
+        }
+        size.size += metadata.len();
+        size.disk_usage += allocated_size(&metadata).unwrap_or(metadata.len());
+        size.file_count += 1;
+    }
+
+    size
+}
+
+/// Newest modification time among the project's own files, skipping
+/// artifact and hidden directories
+fn project_last_modified(project_root: &Path) -> Option<DateTime<Utc>> {
+    WalkBuilder::new(project_root)
+        .standard_filters(false)
+        .hidden(true)
+        .filter_entry(|e| artifact_markers(e.path()).is_none())
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .take(PROJECT_TOUCH_LIMIT)
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
@@ -1,9 +1,16 @@
 // src-tauri/src/scanners/drive_scanner.rs
+use super::file_types::category_for_mime;
+use super::scanner::{ItemSource, ScannedItem, Scanner, ScannerCapabilities};
+use super::versions::{drive_version_clusters, VersionCluster};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::command;
+#[cfg(feature = "desktop")]
+use std::sync::Mutex;
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
 
 // OAuth configuration for Google Drive
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
@@ -41,6 +48,8 @@ pub struct DriveScanResult {
     pub file_types: HashMap<String, usize>,
     pub largest_files: Vec<DriveFile>,
     pub oldest_files: Vec<DriveFile>,
+    #[serde(default)]
+    pub versioned: Vec<VersionCluster>, // Older versions of a document kept beside the newest
     pub scan_timestamp: DateTime<Utc>,
 }
 
@@ -52,10 +61,15 @@ pub struct OAuthToken {
     refresh_token: Option<String>,
 }
 
-// Store OAuth tokens in memory (in production, use secure storage)
-static mut OAUTH_TOKEN: Option<OAuthToken> = None;
+// Holds OAuth tokens in memory (in production, use secure storage)
+#[cfg(feature = "desktop")]
+#[derive(Default)]
+pub struct DriveState {
+    #[allow(dead_code)] // Read by the Drive commands once they are re-enabled
+    token: Mutex<Option<OAuthToken>>,
+}
 
-#[command]
+#[cfg_attr(feature = "desktop", command)]
 pub async fn init_oauth_flow() -> Result<String, String> {
     // TODO: Re-enable Google Drive OAuth flow
     Err("Google Drive integration is temporarily disabled".to_string())
@@ -70,8 +84,12 @@ pub async fn init_oauth_flow() -> Result<String, String> {
     // Ok(auth_url)
 }
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn handle_oauth_callback(_code: String) -> Result<bool, String> {
+pub async fn handle_oauth_callback(
+    _code: String,
+    _state: State<'_, DriveState>,
+) -> Result<bool, String> {
     // TODO: Re-enable Google Drive OAuth callback handling
     Err("Google Drive integration is temporarily disabled".to_string())
     
@@ -100,9 +118,10 @@ pub async fn handle_oauth_callback(_code: String) -> Result<bool, String> {
     //         .map_err(|e| format!("Failed to parse token: {}", e))?;
     // 
     //     // Store token (in production, use secure storage)
-    //     unsafe {
-    //         OAUTH_TOKEN = Some(token);
-    //     }
+    //     *state
+    //         .token
+    //         .lock()
+    //         .map_err(|_| "Drive token store is unavailable".to_string())? = Some(token);
     // 
     //     Ok(true)
     // } else {
@@ -110,12 +129,19 @@ pub async fn handle_oauth_callback(_code: String) -> Result<bool, String> {
     // }
 }
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn scan_drive() -> Result<DriveScanResult, String> {
+pub async fn scan_drive(_state: State<'_, DriveState>) -> Result<DriveScanResult, String> {
     // TODO: Re-enable Google Drive scanning
     Err("Google Drive integration is temporarily disabled".to_string())
     
-    // let token = unsafe { OAUTH_TOKEN.as_ref().ok_or("Not authenticated")? };
+    // let token = state
+    //     .token
+    //     .lock()
+    //     .map_err(|_| "Drive token store is unavailable".to_string())?
+    //     .as_ref()
+    //     .map(|t| t.access_token.clone())
+    //     .ok_or("Not authenticated")?;
     // 
     // let client = reqwest::Client::new();
     // let mut all_files = Vec::new();
@@ -134,7 +160,7 @@ pub async fn scan_drive() -> Result<DriveScanResult, String> {
     // 
     //     let response = client
     //         .get(&url)
-    //         .bearer_auth(&token.access_token)
+    //         .bearer_auth(&token)
     //         .send()
     //         .await
     //         .map_err(|e| format!("Failed to fetch files: {}", e))?;
@@ -187,12 +213,21 @@ pub async fn scan_drive() -> Result<DriveScanResult, String> {
     // Ok(result)
 }
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn get_drive_metadata() -> Result<serde_json::Value, String> {
+pub async fn get_drive_metadata(
+    _state: State<'_, DriveState>,
+) -> Result<serde_json::Value, String> {
     // TODO: Re-enable Google Drive metadata fetching
     Err("Google Drive integration is temporarily disabled".to_string())
     
-    // let token = unsafe { OAUTH_TOKEN.as_ref().ok_or("Not authenticated")? };
+    // let token = state
+    //     .token
+    //     .lock()
+    //     .map_err(|_| "Drive token store is unavailable".to_string())?
+    //     .as_ref()
+    //     .map(|t| t.access_token.clone())
+    //     .ok_or("Not authenticated")?;
     // 
     // let client = reqwest::Client::new();
     // 
@@ -202,7 +237,7 @@ pub async fn get_drive_metadata() -> Result<serde_json::Value, String> {
     //         "{}/about?fields=storageQuota",
     //         GOOGLE_DRIVE_API_URL
     //     ))
-    //     .bearer_auth(&token.access_token)
+    //     .bearer_auth(&token)
     //     .send()
     //     .await
     //     .map_err(|e| format!("Failed to fetch metadata: {}", e))?;
@@ -218,6 +253,61 @@ pub async fn get_drive_metadata() -> Result<serde_json::Value, String> {
     // }
 }
 
+/// Scans the files of the connected Google Drive account
+#[derive(Default)]
+pub struct DriveScanner {
+    access_token: Option<String>,
+}
+
+#[async_trait]
+impl Scanner for DriveScanner {
+    fn source(&self) -> ItemSource {
+        ItemSource::Drive
+    }
+
+    fn capabilities(&self) -> ScannerCapabilities {
+        ScannerCapabilities {
+            requires_auth: true,
+            content_fingerprints: false,
+            access_times: true,
+        }
+    }
+
+    async fn authenticate(&mut self, credential: &str) -> Result<(), String> {
+        self.access_token = Some(credential.to_string());
+        Ok(())
+    }
+
+    async fn scan(&self) -> Result<Vec<ScannedItem>, String> {
+        // TODO: Re-enable Google Drive scanning
+        self.access_token.as_ref().ok_or("Not authenticated")?;
+        Err("Google Drive integration is temporarily disabled".to_string())
+    }
+}
+
+impl From<&DriveFile> for ScannedItem {
+    fn from(file: &DriveFile) -> Self {
+        let parse_time = |time: &str| {
+            DateTime::parse_from_rfc3339(time)
+                .ok()
+                .map(|t| t.with_timezone(&Utc))
+        };
+
+        ScannedItem {
+            id: file.id.clone(),
+            source: ItemSource::Drive,
+            name: file.name.clone(),
+            location: format!("https://drive.google.com/open?id={}", file.id),
+            size_bytes: file.size.unwrap_or(0).max(0) as u64,
+            created_at: parse_time(&file.created_time),
+            modified_at: parse_time(&file.modified_time),
+            accessed_at: file.accessed_time.as_deref().and_then(parse_time),
+            fingerprint: None,
+            owner: None,
+        }
+    }
+}
+
 fn analyze_drive_files(files: Vec<DriveFile>) -> DriveScanResult {
     let mut total_size = 0i64;
     let mut file_types: HashMap<String, usize> = HashMap::new();
@@ -242,26 +332,19 @@ fn analyze_drive_files(files: Vec<DriveFile>) -> DriveScanResult {
     sorted_by_date.sort_by(|a, b| a.modified_time.cmp(&b.modified_time));
     let oldest_files: Vec<DriveFile> = sorted_by_date.into_iter().take(10).collect();
 
+    let versioned = drive_version_clusters(&files);
+
     DriveScanResult {
         total_files: files.len(),
         total_size,
         file_types,
         largest_files,
         oldest_files,
+        versioned,
         scan_timestamp: Utc::now(),
     }
 }
 
 fn get_file_category(mime_type: &str) -> String {
-    match mime_type {
-        t if t.starts_with("image/") => "Images".to_string(),
-        t if t.starts_with("video/") => "Videos".to_string(),
-        t if t.starts_with("audio/") => "Audio".to_string(),
-        t if t.contains("document") => "Documents".to_string(),
-        t if t.contains("spreadsheet") => "Spreadsheets".to_string(),
-        t if t.contains("presentation") => "Presentations".to_string(),
-        t if t.contains("pdf") => "PDFs".to_string(),
-        t if t.contains("folder") => "Folders".to_string(),
-        _ => "Other".to_string(),
-    }
+    category_for_mime(mime_type).to_string()
 }
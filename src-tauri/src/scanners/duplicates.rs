@@ -0,0 +1,201 @@
+// src-tauri/src/scanners/duplicates.rs
+use super::local_scanner::{DuplicateGroup, LocalFile};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::scan_errors::ScanError;
+use super::workers::WorkerPool;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+// Bytes hashed from each end of a file before committing to a full hash
+const PARTIAL_HASH_BYTES: u64 = 16 * 1024;
+
+pub struct DuplicateSearch {
+    pub groups: Vec<DuplicateGroup>,
+    pub files_hashed: usize, // Full hashes computed, excluding ones already known
+    pub errors: Vec<ScanError>, // Files that could not be read for hashing
+}
+
+/// Finds byte-identical files in three stages: group by exact size, then by a
+/// hash of the first and last 16KB, and only fully hash files that still
+/// collide. Files whose `hash` is already set (e.g. from the file index) are
+/// not rehashed, and every file that gets fully hashed has `hash` filled in.
+/// Hashing runs on a pool of `workers` threads and stops early if `monitor`
+/// is cancelled, leaving out groups that were not fully hashed.
+pub fn find_duplicates(
+    files: &mut [LocalFile],
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> DuplicateSearch {
+    let pool = WorkerPool::new(workers, "hashing");
+    monitor.set_phase(ScanPhase::Hashing);
+    let mut files_hashed = 0;
+    let mut errors = Vec::new();
+
+    // Stage 1: only files sharing an exact size can be identical
+    let mut by_size: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, file) in files.iter().enumerate() {
+        if file.size > 0 {
+            by_size.entry(file.size).or_default().push(i);
+        }
+    }
+    let candidates: Vec<usize> = by_size
+        .into_values()
+        .filter(|c| c.len() > 1)
+        .flatten()
+        .collect();
+
+    // Small files are read whole by the partial hash, so hash them fully instead
+    let small: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| files[i].size <= PARTIAL_HASH_BYTES * 2 && files[i].hash.is_none())
+        .collect();
+    files_hashed += hash_files(&pool, files, &small, monitor, &mut errors);
+
+    // Stage 2: group by size and a hash of both ends
+    let shared: &[LocalFile] = files;
+    let partial_keys: Vec<(usize, Result<Option<String>, ScanError>)> =
+        pool.map(&candidates, |&i| {
+            let file = &shared[i];
+            let key = if monitor.is_cancelled() {
+                Ok(None)
+            } else if file.size <= PARTIAL_HASH_BYTES * 2 {
+                Ok(file.hash.clone())
+            } else {
+                let path = Path::new(&file.path);
+                calculate_partial_hash(path, file.size)
+                    .map(Some)
+                    .map_err(|e| ScanError::from_io(path, &e))
+            };
+            (i, key)
+        });
+
+    let mut by_partial: HashMap<(u64, String), Vec<usize>> = HashMap::new();
+    for (i, key) in partial_keys {
+        match key {
+            Ok(Some(key)) => by_partial.entry((files[i].size, key)).or_default().push(i),
+            Ok(None) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+    let colliding: Vec<usize> = by_partial
+        .into_values()
+        .filter(|g| g.len() > 1)
+        .flatten()
+        .collect();
+
+    // Stage 3: fully hash whatever still collides
+    let unhashed: Vec<usize> = colliding
+        .iter()
+        .copied()
+        .filter(|&i| files[i].hash.is_none())
+        .collect();
+    files_hashed += hash_files(&pool, files, &unhashed, monitor, &mut errors);
+
+    let mut by_full_hash: HashMap<String, Vec<usize>> = HashMap::new();
+    for i in colliding {
+        if let Some(hash) = &files[i].hash {
+            by_full_hash.entry(hash.clone()).or_default().push(i);
+        }
+    }
+
+    let groups = by_full_hash
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(hash, indices)| {
+            let files: Vec<LocalFile> = indices.iter().map(|&i| files[i].clone()).collect();
+            let total_size = files[0].size * (files.len() - 1) as u64; // Wasted space
+            DuplicateGroup {
+                hash,
+                total_size,
+                files,
+            }
+        })
+        .collect();
+
+    DuplicateSearch {
+        groups,
+        files_hashed,
+        errors,
+    }
+}
+
+/// Fully hashes `files[i]` for each index on the pool, returning how many
+/// succeeded. Files that could not be read are added to `errors`.
+fn hash_files(
+    pool: &WorkerPool,
+    files: &mut [LocalFile],
+    indices: &[usize],
+    monitor: &ScanMonitor,
+    errors: &mut Vec<ScanError>,
+) -> usize {
+    let shared: &[LocalFile] = files;
+    monitor.will_hash(indices.iter().map(|&i| shared[i].size).sum());
+
+    let results: Vec<(usize, Result<String, std::io::Error>)> = pool.map(indices, |&i| {
+        (i, hash_contents(Path::new(&shared[i].path), Some(monitor)))
+    });
+
+    let mut hashed = 0;
+    for (i, result) in results {
+        match result {
+            Ok(hash) => {
+                files[i].hash = Some(hash);
+                hashed += 1;
+            }
+            // Cancelling interrupts hashing; that's not a problem with the file
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => errors.push(ScanError::from_io(Path::new(&files[i].path), &e)),
+        }
+    }
+    hashed
+}
+
+pub fn calculate_file_hash(path: &Path) -> Result<String, std::io::Error> {
+    hash_contents(path, None)
+}
+
+/// SHA-256 of a whole file, reporting bytes to `monitor` and giving up as
+/// soon as it is cancelled
+fn hash_contents(path: &Path, monitor: Option<&ScanMonitor>) -> Result<String, std::io::Error> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 8192];
+
+    loop {
+        if monitor.is_some_and(|m| m.is_cancelled()) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                "scan cancelled",
+            ));
+        }
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        if let Some(monitor) = monitor {
+            monitor.bytes_hashed(bytes_read as u64, path);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn calculate_partial_hash(path: &Path, size: u64) -> Result<String, std::io::Error> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; PARTIAL_HASH_BYTES as usize];
+
+    file.read_exact(&mut buffer)?;
+    hasher.update(&buffer);
+
+    file.seek(SeekFrom::Start(size - PARTIAL_HASH_BYTES))?;
+    file.read_exact(&mut buffer)?;
+    hasher.update(&buffer);
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,252 @@
+// src-tauri/src/scanners/file_index.rs
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+pub const FILE_INDEX_FILE_NAME: &str = "file_index.sqlite3";
+
+const MAX_CHANGE_SAMPLES: usize = 500;
+
+/// When a root was last indexed, and every file recorded for it
+type IndexSnapshot = (Option<DateTime<Utc>>, HashMap<String, IndexEntry>);
+
+/// What the index remembers about a file between scans
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub size: u64,
+    pub modified_ns: i64,
+    pub inode: u64,
+    pub hash: Option<String>,
+    pub category: Option<String>, // Sniffed from the content, reused while the file is unchanged
+}
+
+impl IndexEntry {
+    /// True when the file on disk looks untouched since it was indexed
+    pub fn is_unchanged(&self, other: &IndexEntry) -> bool {
+        self.size == other.size
+            && self.modified_ns == other.modified_ns
+            && self.inode == other.inode
+    }
+}
+
+/// Files added, removed or modified since the previous scan of the same root
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ScanChanges {
+    pub previous_scan: Option<DateTime<Utc>>,
+    pub added_count: usize,
+    pub removed_count: usize,
+    pub modified_count: usize,
+    pub added: Vec<String>, // Sample paths, capped at 500 each
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    pub files_hashed: usize,
+    pub hashes_reused: usize,
+}
+
+impl ScanChanges {
+    pub fn between(
+        previous_scan: Option<DateTime<Utc>>,
+        previous: &HashMap<String, IndexEntry>,
+        current: &HashMap<String, IndexEntry>,
+    ) -> Self {
+        let mut changes = ScanChanges {
+            previous_scan,
+            ..Default::default()
+        };
+
+        for (path, entry) in current {
+            match previous.get(path) {
+                None => {
+                    changes.added_count += 1;
+                    push_sample(&mut changes.added, path);
+                }
+                Some(old) if !old.is_unchanged(entry) => {
+                    changes.modified_count += 1;
+                    push_sample(&mut changes.modified, path);
+                }
+                Some(_) => {}
+            }
+        }
+
+        for path in previous.keys().filter(|p| !current.contains_key(*p)) {
+            changes.removed_count += 1;
+            push_sample(&mut changes.removed, path);
+        }
+
+        changes.added.sort();
+        changes.removed.sort();
+        changes.modified.sort();
+        changes
+    }
+}
+
+fn push_sample(samples: &mut Vec<String>, path: &str) {
+    if samples.len() < MAX_CHANGE_SAMPLES {
+        samples.push(path.to_string());
+    }
+}
+
+/// Persistent path -> (size, mtime, inode, hash, category) index, so rescans
+/// only hash and sniff files that changed. Clones share one connection, so
+/// scans can move it onto a blocking worker.
+#[derive(Clone)]
+pub struct FileIndex {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl FileIndex {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create file index directory: {}", e))?;
+        }
+        let conn =
+            Connection::open(path).map_err(|e| format!("Failed to open file index: {}", e))?;
+        Self::init(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| format!("Failed to open file index: {}", e))?;
+        Self::init(conn)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS indexed_roots (
+                root TEXT PRIMARY KEY,
+                scanned_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS indexed_files (
+                root TEXT NOT NULL,
+                path TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                modified_ns INTEGER NOT NULL,
+                inode INTEGER NOT NULL,
+                hash TEXT,
+                category TEXT,
+                PRIMARY KEY (root, path)
+            );",
+        )
+        .map_err(|e| format!("Failed to initialize file index: {}", e))?;
+
+        // Indexes created before categories were cached lack the column
+        let has_category = conn
+            .prepare("SELECT category FROM indexed_files LIMIT 0")
+            .is_ok();
+        if !has_category {
+            conn.execute("ALTER TABLE indexed_files ADD COLUMN category TEXT", [])
+                .map_err(|e| format!("Failed to initialize file index: {}", e))?;
+        }
+
+        Ok(FileIndex {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Returns when `root` was last indexed and every file recorded for it
+    pub fn load(&self, root: &str) -> Result<IndexSnapshot, String> {
+        let conn = self.lock()?;
+
+        let scanned_at: Option<String> = conn
+            .query_row(
+                "SELECT scanned_at FROM indexed_roots WHERE root = ?1",
+                params![root],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read file index: {}", e))?;
+        let scanned_at = scanned_at
+            .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+            .map(|t| t.with_timezone(&Utc));
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT path, size, modified_ns, inode, hash, category
+                 FROM indexed_files WHERE root = ?1",
+            )
+            .map_err(|e| format!("Failed to read file index: {}", e))?;
+        let rows = stmt
+            .query_map(params![root], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    IndexEntry {
+                        size: row.get::<_, i64>(1)? as u64,
+                        modified_ns: row.get(2)?,
+                        inode: row.get::<_, i64>(3)? as u64,
+                        hash: row.get(4)?,
+                        category: row.get(5)?,
+                    },
+                ))
+            })
+            .map_err(|e| format!("Failed to read file index: {}", e))?;
+
+        let entries = rows
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| format!("Failed to read file index: {}", e))?;
+
+        Ok((scanned_at, entries))
+    }
+
+    /// Replaces everything recorded for `root` with the latest scan
+    pub fn store(
+        &self,
+        root: &str,
+        scanned_at: DateTime<Utc>,
+        entries: &HashMap<String, IndexEntry>,
+    ) -> Result<(), String> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to update file index: {}", e))?;
+
+        tx.execute("DELETE FROM indexed_files WHERE root = ?1", params![root])
+            .map_err(|e| format!("Failed to update file index: {}", e))?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT INTO indexed_files (root, path, size, modified_ns, inode, hash, category)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )
+                .map_err(|e| format!("Failed to update file index: {}", e))?;
+            for (path, entry) in entries {
+                stmt.execute(params![
+                    root,
+                    path,
+                    entry.size as i64,
+                    entry.modified_ns,
+                    entry.inode as i64,
+                    entry.hash,
+                    entry.category
+                ])
+                .map_err(|e| format!("Failed to update file index: {}", e))?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO indexed_roots (root, scanned_at) VALUES (?1, ?2)",
+            params![root, scanned_at.to_rfc3339_opts(SecondsFormat::Nanos, true)],
+        )
+        .map_err(|e| format!("Failed to update file index: {}", e))?;
+
+        tx.commit()
+            .map_err(|e| format!("Failed to update file index: {}", e))
+    }
+
+    /// Drops the index for `root`, forcing the next scan to rehash everything
+    pub fn forget(&self, root: &str) -> Result<(), String> {
+        let conn = self.lock()?;
+        conn.execute("DELETE FROM indexed_files WHERE root = ?1", params![root])
+            .and_then(|_| conn.execute("DELETE FROM indexed_roots WHERE root = ?1", params![root]))
+            .map(|_| ())
+            .map_err(|e| format!("Failed to clear file index: {}", e))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, String> {
+        self.conn
+            .lock()
+            .map_err(|_| "File index is unavailable".to_string())
+    }
+}
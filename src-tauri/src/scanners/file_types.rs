@@ -0,0 +1,332 @@
+// src-tauri/src/scanners/file_types.rs
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+// Categories shared by local and Drive scans, so their breakdowns line up
+pub const IMAGES: &str = "Images";
+pub const VIDEOS: &str = "Videos";
+pub const AUDIO: &str = "Audio";
+pub const DOCUMENTS: &str = "Documents";
+pub const SPREADSHEETS: &str = "Spreadsheets";
+pub const PRESENTATIONS: &str = "Presentations";
+pub const PDFS: &str = "PDFs";
+pub const TEXT: &str = "Text";
+pub const ARCHIVES: &str = "Archives";
+pub const EXECUTABLES: &str = "Executables";
+pub const DATABASES: &str = "Databases";
+pub const DISK_IMAGES: &str = "Disk Images";
+pub const FOLDERS: &str = "Folders";
+pub const OTHER: &str = "Other";
+
+// Enough for every signature below; tar keeps its magic at offset 257
+const HEADER_BYTES: usize = 512;
+
+// Magic this short turns up by chance in other formats, so it only decides
+// when the extension doesn't
+const WEAK_MAGIC_BYTES: usize = 3;
+
+// (offset, magic bytes, category)
+const SIGNATURES: &[(usize, &[u8], &str)] = &[
+    // Images
+    (0, b"\xFF\xD8\xFF", IMAGES),
+    (0, b"\x89PNG\r\n\x1A\n", IMAGES),
+    (0, b"GIF87a", IMAGES),
+    (0, b"GIF89a", IMAGES),
+    (0, b"II*\0", IMAGES),
+    (0, b"MM\0*", IMAGES),
+    (0, b"8BPS", IMAGES),
+    (8, b"WEBP", IMAGES),
+    (4, b"ftypheic", IMAGES),
+    (4, b"ftypheix", IMAGES),
+    (4, b"ftypmif1", IMAGES),
+    (4, b"ftypavif", IMAGES),
+    // Audio; ahead of video so M4A wins over the generic ftyp brand
+    (4, b"ftypM4A", AUDIO),
+    (0, b"ID3", AUDIO),
+    (0, b"\xFF\xFB", AUDIO),
+    (0, b"\xFF\xF3", AUDIO),
+    (0, b"\xFF\xF2", AUDIO),
+    (0, b"fLaC", AUDIO),
+    (0, b"OggS", AUDIO),
+    (8, b"WAVE", AUDIO),
+    (8, b"AIFF", AUDIO),
+    // Video
+    (4, b"ftyp", VIDEOS),             // Any other ISO media brand: mp4, mov, 3gp
+    (0, b"\x1A\x45\xDF\xA3", VIDEOS), // Matroska and WebM
+    (8, b"AVI ", VIDEOS),
+    (0, b"FLV\x01", VIDEOS),
+    (0, b"\0\0\x01\xBA", VIDEOS), // MPEG program stream
+    (0, b"\0\0\x01\xB3", VIDEOS),
+    // Documents
+    (0, b"%PDF-", PDFS),
+    (0, b"{\\rtf", DOCUMENTS),
+    // Archives; zip and OLE containers are refined by extension below
+    (0, b"PK\x03\x04", ARCHIVES),
+    (0, b"PK\x05\x06", ARCHIVES),
+    (0, b"\x1F\x8B", ARCHIVES),
+    (0, b"7z\xBC\xAF\x27\x1C", ARCHIVES),
+    (0, b"Rar!\x1A\x07", ARCHIVES),
+    (0, b"BZh", ARCHIVES),
+    (0, b"\xFD7zXZ\0", ARCHIVES),
+    (0, b"\x28\xB5\x2F\xFD", ARCHIVES), // zstd
+    (0, b"xar!", ARCHIVES),
+    (257, b"ustar", ARCHIVES),
+    // Executables
+    (0, b"\x7FELF", EXECUTABLES),
+    (0, b"MZ", EXECUTABLES),
+    (0, b"\xFE\xED\xFA\xCE", EXECUTABLES),
+    (0, b"\xFE\xED\xFA\xCF", EXECUTABLES),
+    (0, b"\xCE\xFA\xED\xFE", EXECUTABLES),
+    (0, b"\xCF\xFA\xED\xFE", EXECUTABLES),
+    (0, b"\xCA\xFE\xBA\xBE", EXECUTABLES), // Universal Mach-O or Java class
+    (0, b"\0asm", EXECUTABLES),
+    // Databases
+    (0, b"SQLite format 3\0", DATABASES),
+    (4, b"Standard Jet DB", DATABASES),
+    (4, b"Standard ACE DB", DATABASES),
+    // VM disk images
+    (0, b"QFI\xFB", DISK_IMAGES),
+    (0, b"KDMV", DISK_IMAGES),
+    (0, b"vhdxfile", DISK_IMAGES),
+    (0, b"conectix", DISK_IMAGES),
+    (0, b"<<< Oracle VM VirtualBox Disk Image >>>", DISK_IMAGES),
+];
+
+// Office documents, packages and the like are zip files inside
+const ZIP_CONTAINERS: &[(&str, &str)] = &[
+    ("docx", DOCUMENTS),
+    ("odt", DOCUMENTS),
+    ("epub", DOCUMENTS),
+    ("pages", DOCUMENTS),
+    ("xlsx", SPREADSHEETS),
+    ("ods", SPREADSHEETS),
+    ("numbers", SPREADSHEETS),
+    ("pptx", PRESENTATIONS),
+    ("odp", PRESENTATIONS),
+    ("key", PRESENTATIONS),
+    ("jar", EXECUTABLES),
+    ("apk", EXECUTABLES),
+    ("aab", EXECUTABLES),
+];
+
+// Pre-2007 Office files and installers share the OLE compound file format
+const OLE_MAGIC: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+const OLE_CONTAINERS: &[(&str, &str)] = &[
+    ("xls", SPREADSHEETS),
+    ("ppt", PRESENTATIONS),
+    ("msi", EXECUTABLES),
+    ("msg", DOCUMENTS),
+];
+
+// Used when the content has no known signature, or could not be read
+const EXTENSIONS: &[(&str, &[&str])] = &[
+    (
+        IMAGES,
+        &[
+            "jpg", "jpeg", "png", "gif", "webp", "bmp", "tif", "tiff", "heic", "heif", "avif",
+            "svg", "ico", "psd", "raw", "cr2", "nef", "arw", "dng",
+        ],
+    ),
+    (
+        VIDEOS,
+        &[
+            "mp4", "m4v", "mov", "mkv", "webm", "avi", "wmv", "flv", "mpg", "mpeg", "3gp",
+        ],
+    ),
+    (
+        AUDIO,
+        &[
+            "mp3", "m4a", "aac", "flac", "ogg", "opus", "wav", "aiff", "wma",
+        ],
+    ),
+    (DOCUMENTS, &["doc", "docx", "odt", "rtf", "pages", "epub"]),
+    (
+        SPREADSHEETS,
+        &["xls", "xlsx", "ods", "numbers", "csv", "tsv"],
+    ),
+    (PRESENTATIONS, &["ppt", "pptx", "odp", "key"]),
+    (PDFS, &["pdf"]),
+    (
+        TEXT,
+        &[
+            "txt", "md", "markdown", "rst", "log", "json", "xml", "yaml", "yml", "toml", "ini",
+            "html", "htm", "css",
+        ],
+    ),
+    (
+        ARCHIVES,
+        &[
+            "zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar", "pkg",
+        ],
+    ),
+    (
+        EXECUTABLES,
+        &[
+            "exe", "dll", "so", "dylib", "msi", "app", "deb", "rpm", "apk", "jar", "wasm",
+        ],
+    ),
+    (DATABASES, &["db", "sqlite", "sqlite3", "mdb", "accdb"]),
+    (
+        DISK_IMAGES,
+        &["iso", "img", "dmg", "vmdk", "vdi", "vhd", "vhdx", "qcow2"],
+    ),
+];
+
+/// Category of the file at `path` from its first bytes, falling back to its
+/// extension. Reading leaves the access time as it was where the platform
+/// allows, since stale file detection depends on it.
+pub fn detect_category(path: &Path, extension: &str) -> &'static str {
+    let extension = extension.to_lowercase();
+    let header = read_header(path);
+
+    if let Some(header) = &header {
+        if let Some(category) = sniff(header, &extension) {
+            return category;
+        }
+    }
+    if let Some(category) = category_for_extension(&extension) {
+        return category;
+    }
+    match header {
+        Some(header) if looks_like_text(&header) => TEXT,
+        _ => OTHER,
+    }
+}
+
+/// Category of a file from its content, refined by `extension` for formats
+/// that wrap others. Short signatures are ignored when the extension is a
+/// known one.
+pub fn sniff(header: &[u8], extension: &str) -> Option<&'static str> {
+    if header.starts_with(OLE_MAGIC) {
+        return Some(lookup(OLE_CONTAINERS, extension).unwrap_or(DOCUMENTS));
+    }
+
+    let (_, magic, category) = SIGNATURES
+        .iter()
+        .find(|(offset, magic, _)| header.get(*offset..).is_some_and(|h| h.starts_with(magic)))?;
+    if magic.len() <= WEAK_MAGIC_BYTES && category_for_extension(extension).is_some() {
+        return None;
+    }
+
+    if header.starts_with(b"PK") {
+        return Some(lookup(ZIP_CONTAINERS, extension).unwrap_or(ARCHIVES));
+    }
+    Some(*category)
+}
+
+pub fn category_for_extension(extension: &str) -> Option<&'static str> {
+    let extension = extension.to_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map(|(category, _)| *category)
+}
+
+/// Category of a Drive file from its MIME type
+pub fn category_for_mime(mime_type: &str) -> &'static str {
+    match mime_type {
+        t if t.starts_with("image/") => IMAGES,
+        t if t.starts_with("video/") => VIDEOS,
+        t if t.starts_with("audio/") => AUDIO,
+        t if t.contains("document") => DOCUMENTS,
+        t if t.contains("spreadsheet") || t == "text/csv" => SPREADSHEETS,
+        t if t.contains("presentation") => PRESENTATIONS,
+        t if t.contains("pdf") => PDFS,
+        t if t.contains("folder") => FOLDERS,
+        t if t.contains("sqlite") || t.contains("msaccess") => DATABASES,
+        t if t.contains("diskimage") || t.contains("iso9660") || t.contains("virtualbox") => {
+            DISK_IMAGES
+        }
+        t if t.contains("executable")
+            || t.contains("msdownload")
+            || t.contains("mach-binary")
+            || t.contains("android.package-archive") =>
+        {
+            EXECUTABLES
+        }
+        t if t.contains("zip")
+            || t.contains("tar")
+            || t.contains("7z")
+            || t.contains("rar")
+            || t.contains("bzip")
+            || t.contains("zstd")
+            || t.contains("x-xz") =>
+        {
+            ARCHIVES
+        }
+        t if t.starts_with("text/") => TEXT,
+        _ => OTHER,
+    }
+}
+
+fn lookup(table: &[(&str, &'static str)], extension: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, category)| *category)
+}
+
+/// No NUL bytes and valid UTF-8, allowing a character cut off at the end
+fn looks_like_text(header: &[u8]) -> bool {
+    if header.is_empty() || header.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(header) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    use std::os::unix::fs::OpenOptionsExt;
+    let file = match fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOATIME)
+        .open(path)
+    {
+        Ok(file) => file,
+        // O_NOATIME is only allowed on files the user owns; others are read
+        // anyway, which may bump their access time
+        Err(e) if e.raw_os_error() == Some(libc::EPERM) => fs::File::open(path).ok()?,
+        Err(_) => return None,
+    };
+    read_start(&file)
+}
+
+// Elsewhere reading can bump the access time, so put it back afterwards
+#[cfg(not(target_os = "linux"))]
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    let accessed = fs::metadata(path).ok()?.accessed().ok()?;
+    let file = open_restorable(path)?;
+    let header = read_start(&file);
+    let _ = file.set_times(fs::FileTimes::new().set_accessed(accessed));
+    header
+}
+
+// Windows needs write-attribute access on the handle to set times
+#[cfg(windows)]
+fn open_restorable(path: &Path) -> Option<fs::File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    const GENERIC_READ: u32 = 0x8000_0000;
+    const FILE_WRITE_ATTRIBUTES: u32 = 0x100;
+    fs::OpenOptions::new()
+        .read(true)
+        .access_mode(GENERIC_READ | FILE_WRITE_ATTRIBUTES)
+        .open(path)
+        .ok()
+}
+
+#[cfg(all(not(target_os = "linux"), not(windows)))]
+fn open_restorable(path: &Path) -> Option<fs::File> {
+    fs::File::open(path).ok()
+}
+
+fn read_start(file: &fs::File) -> Option<Vec<u8>> {
+    let mut header = Vec::with_capacity(HEADER_BYTES);
+    file.take(HEADER_BYTES as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    Some(header)
+}
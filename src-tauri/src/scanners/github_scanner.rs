@@ -1,9 +1,19 @@
 // src-tauri/src/scanners/github_scanner.rs
+#[cfg(feature = "desktop")]
+use super::progress::{event_sink, ActiveScans};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::scanner::{ItemSource, ScannedItem, Scanner, ScannerCapabilities};
+#[cfg(feature = "desktop")]
+use crate::history::ScanHistory;
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::command;
+#[cfg(feature = "desktop")]
+use std::sync::Mutex;
+#[cfg(feature = "desktop")]
+use tauri::{command, AppHandle, State};
 
 const GITHUB_API_URL: &str = "https://api.github.com";
 const STALE_REPO_DAYS: i64 = 180; // 6 months
@@ -55,13 +65,168 @@ pub struct GitHubScanResult {
     pub largest_repos: Vec<GitHubRepo>,
     pub orphaned_branches: HashMap<String, Vec<Branch>>,
     pub scan_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub incomplete: bool, // The scan was cancelled before every page or branch was fetched
 }
 
-// Store GitHub token in memory (in production, use secure storage)
-static mut GITHUB_TOKEN: Option<String> = None;
+// Holds the GitHub token in memory (in production, use secure storage)
+#[cfg(feature = "desktop")]
+#[derive(Default)]
+pub struct GitHubState {
+    token: Mutex<Option<String>>,
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn authenticate_github(
+    token: String,
+    state: State<'_, GitHubState>,
+) -> Result<bool, String> {
+    validate_token(&token).await?;
+
+    // Store token (in production, use secure storage)
+    *state
+        .token
+        .lock()
+        .map_err(|_| "GitHub token store is unavailable".to_string())? = Some(token);
+    Ok(true)
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn scan_repositories(
+    app: AppHandle,
+    scan_id: Option<String>,
+    state: State<'_, GitHubState>,
+    history: State<'_, ScanHistory>,
+    scans: State<'_, ActiveScans>,
+) -> Result<GitHubScanResult, String> {
+    let token = state
+        .token
+        .lock()
+        .map_err(|_| "GitHub token store is unavailable".to_string())?
+        .clone()
+        .ok_or("Not authenticated with GitHub")?;
+
+    let monitor = scans.start(scan_id, Some(event_sink(app)))?;
+    let result = scan_repositories_with_token(&token, &monitor).await;
+    scans.finish(&monitor);
+    let result = result?;
+
+    if !result.incomplete {
+        if let Err(e) = history.record_github_scan(&result) {
+            eprintln!("Failed to record scan history: {}", e);
+        }
+    }
+
+    Ok(result)
+}
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn authenticate_github(token: String) -> Result<bool, String> {
+pub async fn get_stale_repos(
+    app: AppHandle,
+    scan_id: Option<String>,
+    state: State<'_, GitHubState>,
+    history: State<'_, ScanHistory>,
+    scans: State<'_, ActiveScans>,
+) -> Result<Vec<GitHubRepo>, String> {
+    let scan_result = scan_repositories(app, scan_id, state, history, scans).await?;
+    Ok(scan_result.stale_repos)
+}
+
+/// Fetches and analyzes every repository visible to `token`. If `monitor`
+/// is cancelled, analyzes only the repositories fetched so far.
+pub async fn scan_repositories_with_token(
+    token: &str,
+    monitor: &ScanMonitor,
+) -> Result<GitHubScanResult, String> {
+    let client = reqwest::Client::new();
+    let all_repos = fetch_all_repos(&client, token, monitor).await?;
+
+    // Analyze repositories and find orphaned branches
+    let mut orphaned_branches = HashMap::new();
+    let cutoff_date = Utc::now() - Duration::days(STALE_REPO_DAYS);
+
+    for repo in &all_repos {
+        if monitor.is_cancelled() {
+            break;
+        }
+        if repo.pushed_at < cutoff_date && !repo.archived {
+            monitor.checking_repo(&repo.full_name);
+            // Check for orphaned branches in stale repos
+            if let Ok(branches) = fetch_repo_branches(&client, token, &repo.full_name).await {
+                let orphaned: Vec<Branch> = branches
+                    .into_iter()
+                    .filter(|b| b.name != repo.default_branch && b.ahead_by > 0)
+                    .collect();
+
+                if !orphaned.is_empty() {
+                    orphaned_branches.insert(repo.full_name.clone(), orphaned);
+                }
+            }
+        }
+    }
+
+    monitor.set_phase(ScanPhase::Analyzing);
+    let mut result = analyze_repositories(all_repos, orphaned_branches);
+    result.incomplete = monitor.is_cancelled();
+    Ok(result)
+}
+
+/// Scans the repositories of the authenticated GitHub user
+#[derive(Default)]
+pub struct GitHubScanner {
+    token: Option<String>,
+}
+
+#[async_trait]
+impl Scanner for GitHubScanner {
+    fn source(&self) -> ItemSource {
+        ItemSource::GitHub
+    }
+
+    fn capabilities(&self) -> ScannerCapabilities {
+        ScannerCapabilities {
+            requires_auth: true,
+            content_fingerprints: false,
+            access_times: false,
+        }
+    }
+
+    async fn authenticate(&mut self, credential: &str) -> Result<(), String> {
+        validate_token(credential).await?;
+        self.token = Some(credential.to_string());
+        Ok(())
+    }
+
+    async fn scan(&self) -> Result<Vec<ScannedItem>, String> {
+        let token = self.token.as_ref().ok_or("Not authenticated with GitHub")?;
+        let client = reqwest::Client::new();
+        let repos = fetch_all_repos(&client, token, &ScanMonitor::detached()).await?;
+
+        Ok(repos.iter().map(ScannedItem::from).collect())
+    }
+}
+
+impl From<&GitHubRepo> for ScannedItem {
+    fn from(repo: &GitHubRepo) -> Self {
+        ScannedItem {
+            id: repo.id.to_string(),
+            source: ItemSource::GitHub,
+            name: repo.full_name.clone(),
+            location: format!("https://github.com/{}", repo.full_name),
+            size_bytes: repo.size.max(0) as u64 * 1024,
+            created_at: Some(repo.created_at),
+            modified_at: Some(repo.pushed_at),
+            accessed_at: None,
+            fingerprint: None,
+            owner: Some(repo.owner.clone()),
+        }
+    }
+}
+
+async fn validate_token(token: &str) -> Result<(), String> {
     // Validate token by making a test API call
     let client = reqwest::Client::new();
 
@@ -74,30 +239,23 @@ pub async fn authenticate_github(token: String) -> Result<bool, String> {
         .map_err(|e| format!("Failed to validate token: {}", e))?;
 
     if response.status().is_success() {
-        // Store token (in production, use secure storage)
-        unsafe {
-            GITHUB_TOKEN = Some(token);
-        }
-        Ok(true)
+        Ok(())
     } else {
         Err("Invalid GitHub token".to_string())
     }
 }
 
-#[command]
-pub async fn scan_repositories() -> Result<GitHubScanResult, String> {
-    let token = unsafe {
-        GITHUB_TOKEN
-            .as_ref()
-            .ok_or("Not authenticated with GitHub")?
-    };
-
-    let client = reqwest::Client::new();
+async fn fetch_all_repos(
+    client: &reqwest::Client,
+    token: &str,
+    monitor: &ScanMonitor,
+) -> Result<Vec<GitHubRepo>, String> {
     let mut all_repos = Vec::new();
     let mut page = 1;
+    monitor.set_phase(ScanPhase::Fetching);
 
     // Fetch all repositories
-    loop {
+    while !monitor.is_cancelled() {
         let url = format!(
             "{}/user/repos?page={}&per_page=100&type=all",
             GITHUB_API_URL, page
@@ -124,43 +282,20 @@ pub async fn scan_repositories() -> Result<GitHubScanResult, String> {
             break;
         }
 
+        let fetched = all_repos.len();
         for repo in repos {
             if let Some(parsed_repo) = parse_repo(&repo) {
                 all_repos.push(parsed_repo);
             }
         }
+        if let Some(last) = all_repos.last() {
+            monitor.repos_fetched((all_repos.len() - fetched) as u64, &last.full_name);
+        }
 
         page += 1;
     }
 
-    // Analyze repositories and find orphaned branches
-    let mut orphaned_branches = HashMap::new();
-    let cutoff_date = Utc::now() - Duration::days(STALE_REPO_DAYS);
-
-    for repo in &all_repos {
-        if repo.pushed_at < cutoff_date && !repo.archived {
-            // Check for orphaned branches in stale repos
-            if let Ok(branches) = fetch_repo_branches(&client, &token, &repo.full_name).await {
-                let orphaned: Vec<Branch> = branches
-                    .into_iter()
-                    .filter(|b| b.name != repo.default_branch && b.ahead_by > 0)
-                    .collect();
-
-                if !orphaned.is_empty() {
-                    orphaned_branches.insert(repo.full_name.clone(), orphaned);
-                }
-            }
-        }
-    }
-
-    let result = analyze_repositories(all_repos, orphaned_branches);
-    Ok(result)
-}
-
-#[command]
-pub async fn get_stale_repos() -> Result<Vec<GitHubRepo>, String> {
-    let scan_result = scan_repositories().await?;
-    Ok(scan_result.stale_repos)
+    Ok(all_repos)
 }
 
 fn parse_repo(data: &serde_json::Value) -> Option<GitHubRepo> {
@@ -294,5 +429,6 @@ fn analyze_repositories(
         largest_repos,
         orphaned_branches,
         scan_timestamp: Utc::now(),
+        incomplete: false,
     }
 }
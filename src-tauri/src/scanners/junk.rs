@@ -0,0 +1,123 @@
+// src-tauri/src/scanners/junk.rs
+use super::local_scanner::LocalFile;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Empty files whose presence is the point, so they are never reported
+const MEANINGFUL_EMPTY_FILES: &[&str] =
+    &["__init__.py", "py.typed", ".gitkeep", ".keep", ".nojekyll"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrokenSymlink {
+    pub path: String,
+    pub target: String, // Where the link points, which no longer exists
+}
+
+/// Clutter that takes no space but makes a tree harder to navigate
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StructuralJunk {
+    pub empty_directories: Vec<String>, // Topmost folder of each chain holding nothing but empty folders
+    pub empty_directory_count: usize,   // Every empty folder, including the ones nested in a chain
+    pub zero_byte_files: Vec<String>,
+    pub broken_symlinks: Vec<BrokenSymlink>,
+}
+
+impl StructuralJunk {
+    pub fn count(&self) -> usize {
+        self.empty_directory_count + self.zero_byte_files.len() + self.broken_symlinks.len()
+    }
+}
+
+/// Collects the empty folders below `root` among the walked `directories`,
+/// the zero-byte `files`, and `broken_symlinks` found while walking. A folder
+/// only counts as empty if, on disk, it holds nothing but other empty folders;
+/// files the scan rules skipped still count as content.
+pub fn find_structural_junk(
+    root: &Path,
+    directories: &[PathBuf],
+    files: &[LocalFile],
+    broken_symlinks: Vec<BrokenSymlink>,
+) -> StructuralJunk {
+    // Folders with a walked file somewhere below them are certainly not empty
+    let mut occupied: HashSet<&Path> = HashSet::new();
+    for file in files {
+        for folder in Path::new(&file.path).ancestors().skip(1) {
+            if !occupied.insert(folder) {
+                break;
+            }
+        }
+    }
+    for link in &broken_symlinks {
+        for folder in Path::new(&link.path).ancestors().skip(1) {
+            if !occupied.insert(folder) {
+                break;
+            }
+        }
+    }
+
+    // Deepest first, so a folder's subfolders are settled before it is
+    let mut candidates: Vec<&PathBuf> = directories
+        .iter()
+        .filter(|dir| dir.as_path() != root && !occupied.contains(dir.as_path()))
+        .collect();
+    candidates.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+    let mut empty: HashSet<&Path> = HashSet::new();
+    for dir in candidates {
+        if holds_only(dir, &empty) {
+            empty.insert(dir);
+        }
+    }
+
+    let mut empty_directories: Vec<String> = empty
+        .iter()
+        .filter(|dir| dir.parent().is_none_or(|parent| !empty.contains(parent)))
+        .map(|dir| dir.to_string_lossy().to_string())
+        .collect();
+    empty_directories.sort();
+
+    let zero_byte_files = files
+        .iter()
+        .filter(|f| f.size == 0 && !MEANINGFUL_EMPTY_FILES.contains(&f.name.as_str()))
+        .map(|f| f.path.clone())
+        .collect();
+
+    StructuralJunk {
+        empty_directories,
+        empty_directory_count: empty.len(),
+        zero_byte_files,
+        broken_symlinks,
+    }
+}
+
+/// Whether every entry of `dir` is a folder already found to be empty
+fn holds_only(dir: &Path, empty: &HashSet<&Path>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    for entry in entries {
+        let Ok(entry) = entry else {
+            return false;
+        };
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if !is_dir || !empty.contains(entry.path().as_path()) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The link at `path`, if it is a symlink whose target is missing
+pub fn broken_symlink(path: &Path) -> Option<BrokenSymlink> {
+    let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    if !is_link || fs::metadata(path).is_ok() {
+        return None;
+    }
+    let target = fs::read_link(path).ok()?;
+    Some(BrokenSymlink {
+        path: path.to_string_lossy().to_string(),
+        target: target.to_string_lossy().to_string(),
+    })
+}
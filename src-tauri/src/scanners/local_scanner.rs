@@ -1,13 +1,35 @@
 // src-tauri/src/scanners/local_scanner.rs
+use super::archives::{find_extracted_archives, ArchiveReport};
+use super::build_artifacts::{find_build_artifacts, ArtifactScanResult};
+use super::duplicates::find_duplicates;
+use super::file_index::{FileIndex, IndexEntry, ScanChanges};
+use super::file_types::detect_category;
+use super::junk::{broken_symlink, find_structural_junk, BrokenSymlink, StructuralJunk};
+#[cfg(feature = "desktop")]
+use super::progress::{event_sink, ActiveScans};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::rules::{RuleMatcher, ScanRules};
+use super::scan_errors::{error_path, ErrorCounts, ScanError, MAX_LISTED_ERRORS};
+use super::scanner::{ItemSource, ScannedItem, Scanner, ScannerCapabilities};
+use super::similar_images::{find_similar_images, ImageSimilarity};
+use super::similar_text::{find_similar_text, TextSimilarity};
+use super::size_tree::{build_tree, DirNode, SizeTrees, TreeOptions};
+use super::temp_files::{TempClassifier, TemporarySummary};
+use super::versions::{local_version_clusters, VersionCluster};
+use super::workers::WorkerConfig;
+#[cfg(feature = "desktop")]
+use crate::history::ScanHistory;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use ignore::{WalkBuilder, WalkState};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Read;
-use std::path::Path;
-use tauri::command;
-use walkdir::{DirEntry, WalkDir};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::UNIX_EPOCH;
+#[cfg(feature = "desktop")]
+use tauri::{command, AppHandle, State};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LocalFile {
@@ -19,48 +41,229 @@ pub struct LocalFile {
     pub accessed_time: Option<DateTime<Utc>>,
     pub is_hidden: bool,
     pub hash: Option<String>,
+    #[serde(default)]
+    pub allocated_size: Option<u64>, // Bytes taken on disk; less than size for sparse files
+    #[serde(default)]
+    pub category: String, // Images, Videos, Archives, ... from the file's content where it could be read
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FolderStats {
     pub total_files: usize,
     pub total_directories: usize,
-    pub total_size: u64,
-    pub file_types: HashMap<String, usize>,
+    pub total_size: u64, // Each file counted once, however many paths lead to it
+    #[serde(default)]
+    pub disk_usage: u64, // Allocated bytes, so sparse files count only what they store
+    pub file_types: HashMap<String, usize>, // Files per category, as in Drive scans
+    #[serde(default)]
+    pub extensions: HashMap<String, usize>, // Files per lowercase extension
     pub largest_files: Vec<LocalFile>,
     pub duplicates: Vec<DuplicateGroup>,
+    #[serde(default)]
+    pub near_duplicates: Vec<NearDuplicateGroup>, // Only filled when a similarity pass was requested
+    #[serde(default)]
+    pub archives: Vec<ArchiveReport>, // Archives also found unpacked; only when archives were inspected
     pub unused_files: Vec<LocalFile>, // Files not accessed in 6+ months
+    #[serde(default)]
+    pub hardlinks: Vec<HardlinkSet>,
+    #[serde(default)]
+    pub temporary: TemporarySummary, // Temp and cache files, safe to delete
+    #[serde(default)]
+    pub versioned: Vec<VersionCluster>, // Older versions of a document kept beside the newest
+    #[serde(default)]
+    pub structural_junk: StructuralJunk, // Empty folders, zero-byte files and broken symlinks
+    #[serde(default)]
+    pub tree: Option<DirNode>, // Only filled when a tree was requested; deeper levels come from get_subtree
     pub scan_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub changes: Option<ScanChanges>, // Since the previous indexed scan of this path
+    #[serde(default)]
+    pub incomplete: bool, // The scan was cancelled; totals cover only what was reached
+    #[serde(default)]
+    pub errors: Vec<ScanError>, // Paths that could not be read, the first MAX_LISTED_ERRORS of them
+    #[serde(default)]
+    pub error_counts: ErrorCounts, // Every error, including ones left out of `errors`
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DuplicateGroup {
     pub hash: String,
-    pub total_size: u64,
+    pub total_size: u64, // Wasted space: every copy beyond the first
     pub files: Vec<LocalFile>,
 }
 
-const SKIP_DIRECTORIES: &[&str] = &[
-    "node_modules",
-    ".git",
-    ".vscode",
-    "target",
-    "build",
-    "dist",
-    "$RECYCLE.BIN",
-    "System Volume Information",
-    "Windows",
-    "Program Files",
-    "Program Files (x86)",
-    "ProgramData",
-];
+/// Paths in a scan that lead to the same file on disk: hard links, or a file
+/// reached again through a followed symlink or a bind mount. Only the first
+/// path counts towards totals and duplicate detection.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HardlinkSet {
+    pub device: u64,
+    pub inode: u64,
+    pub size: u64,
+    pub link_count: u64, // Hard links on the whole filesystem, including ones outside the scan
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NearDuplicateKind {
+    Image, // Resized or re-encoded copies of the same picture
+    Text,  // Lightly edited copies of the same document, source file or CSV
+}
+
+/// Files that are not byte-identical but hold the same content, e.g. a photo
+/// and a resized copy of it. The first file is the one to keep.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearDuplicateGroup {
+    pub kind: NearDuplicateKind,
+    pub similarity: f64, // Lowest similarity of any file to the first, 0 to 1
+    pub total_size: u64, // Size of every file but the first
+    pub files: Vec<LocalFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateScanResult {
+    pub groups: Vec<DuplicateGroup>,
+    pub incomplete: bool, // The scan was cancelled before every candidate was hashed
+}
+
+/// Knobs for a local scan
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanOptions {
+    pub full_rescan: bool, // Ignore hashes and categories remembered by the file index
+    pub workers: WorkerConfig,
+    pub rules: ScanRules,
+    pub inspect_archives: bool, // Look inside zip and tar archives for contents already unpacked
+    pub similar_images: Option<ImageSimilarity>, // Group look-alike images; skipped when unset
+    pub similar_text: Option<TextSimilarity>, // Group mostly identical text files; skipped when unset
+    pub tree: Option<TreeOptions>,            // Return folder sizes as a tree; skipped when unset
+}
+
+/// Scans one or more local directory trees
+pub struct LocalScanner {
+    pub roots: Vec<String>,
+    pub workers: WorkerConfig,
+    pub rules: ScanRules,
+}
+
+#[async_trait]
+impl Scanner for LocalScanner {
+    fn source(&self) -> ItemSource {
+        ItemSource::Local
+    }
+
+    fn capabilities(&self) -> ScannerCapabilities {
+        ScannerCapabilities {
+            requires_auth: false,
+            content_fingerprints: true,
+            access_times: true,
+        }
+    }
+
+    async fn authenticate(&mut self, _credential: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn scan(&self) -> Result<Vec<ScannedItem>, String> {
+        let mut items = Vec::new();
+
+        for root in &self.roots {
+            let scan_path = Path::new(root);
+            if !scan_path.is_dir() {
+                return Err(format!("{} is not a directory", root));
+            }
+
+            // Fingerprint every file that could have a duplicate
+            let root = scan_path.to_path_buf();
+            let rules = self.rules.compile(scan_path)?;
+            let workers = self.workers.workers_for(scan_path);
+            let files = run_blocking(move || {
+                let monitor = ScanMonitor::detached();
+                let mut walk =
+                    collect_files(&root, &rules, &HashMap::new(), false, workers, &monitor);
+                find_duplicates(&mut walk.files, workers, &monitor);
+                walk.files
+            })
+            .await?;
+            items.extend(files.iter().map(ScannedItem::from));
+        }
+
+        Ok(items)
+    }
+}
+
+impl From<&LocalFile> for ScannedItem {
+    fn from(file: &LocalFile) -> Self {
+        ScannedItem {
+            id: file.path.clone(),
+            source: ItemSource::Local,
+            name: file.name.clone(),
+            location: file.path.clone(),
+            size_bytes: file.size,
+            created_at: None,
+            modified_at: Some(file.modified_time),
+            accessed_at: file.accessed_time,
+            fingerprint: file.hash.clone(),
+            owner: None,
+        }
+    }
+}
 
 const LARGE_FILE_THRESHOLD: u64 = 100 * 1024 * 1024; // 100MB
 const UNUSED_DAYS_THRESHOLD: i64 = 180; // 6 months
 
+#[cfg(feature = "desktop")]
+#[allow(clippy::too_many_arguments)]
 #[command]
-pub async fn scan_directory(path: String) -> Result<FolderStats, String> {
-    let scan_path = Path::new(&path);
+pub async fn scan_directory(
+    app: AppHandle,
+    path: String,
+    options: Option<ScanOptions>,
+    scan_id: Option<String>,
+    history: State<'_, ScanHistory>,
+    file_index: State<'_, FileIndex>,
+    scans: State<'_, ActiveScans>,
+    trees: State<'_, SizeTrees>,
+) -> Result<FolderStats, String> {
+    let monitor = scans.start(scan_id, Some(event_sink(app)))?;
+    let stats = scan_path(
+        &path,
+        Some(&file_index),
+        Some(&trees),
+        &options.unwrap_or_default(),
+        &monitor,
+    )
+    .await;
+    scans.finish(&monitor);
+    let stats = stats?;
+
+    // Partial scans would show up as sudden drops in the trend
+    if !stats.incomplete {
+        if let Err(e) = history.record_local_scan(&path, &stats) {
+            eprintln!("Failed to record scan history: {}", e);
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Scans the directory tree at `path` for bloat. With an `index`, hashes and
+/// categories of files whose size, mtime and inode are unchanged since the
+/// last scan are reused, unless `options.full_rescan` is set. Walking and
+/// hashing run on a worker pool sized for the storage behind `path`. If
+/// `monitor` is cancelled the scan stops early and returns what it found,
+/// flagged `incomplete`.
+/// When `options.tree` is set the full folder tree is kept in `trees`, if
+/// given, for later `get_subtree` calls.
+pub async fn scan_path(
+    path: &str,
+    index: Option<&FileIndex>,
+    trees: Option<&SizeTrees>,
+    options: &ScanOptions,
+    monitor: &ScanMonitor,
+) -> Result<FolderStats, String> {
+    let scan_path = Path::new(path);
 
     if !scan_path.exists() {
         return Err("Directory does not exist".to_string());
@@ -70,49 +273,120 @@ pub async fn scan_directory(path: String) -> Result<FolderStats, String> {
         return Err("Path is not a directory".to_string());
     }
 
-    let mut all_files = Vec::new();
-    let mut total_directories = 0;
-    let mut file_hashes: HashMap<String, Vec<LocalFile>> = HashMap::new();
+    // Index by canonical path so the same tree matches however it was named
+    let root = fs::canonicalize(scan_path)
+        .map_err(|e| format!("Failed to resolve {}: {}", path, e))?
+        .to_string_lossy()
+        .to_string();
 
-    // Walk directory tree
-    for entry in WalkDir::new(scan_path)
-        .into_iter()
-        .filter_entry(|e| !is_skip_directory(e))
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-
-        if path.is_dir() {
-            total_directories += 1;
-        } else if path.is_file() {
-            if let Some(file_info) = process_file(&entry) {
-                // Calculate hash for duplicate detection (only for smaller files)
-                if file_info.size < 50 * 1024 * 1024 {
-                    // 50MB limit for hashing
-                    if let Ok(hash) = calculate_file_hash(path) {
-                        let mut file_with_hash = file_info.clone();
-                        file_with_hash.hash = Some(hash.clone());
-                        file_hashes
-                            .entry(hash)
-                            .or_insert_with(Vec::new)
-                            .push(file_with_hash.clone());
-                        all_files.push(file_with_hash);
-                    } else {
-                        all_files.push(file_info);
-                    }
-                } else {
-                    all_files.push(file_info);
-                }
+    let rules = options.rules.compile(Path::new(&root))?;
+    if let Some(similar_images) = &options.similar_images {
+        similar_images.validate()?;
+    }
+    if let Some(similar_text) = &options.similar_text {
+        similar_text.validate()?;
+    }
+    if let Some(tree) = &options.tree {
+        tree.validate()?;
+    }
+    let similar_images = options.similar_images.clone();
+    let similar_text = options.similar_text.clone();
+    let workers = options.workers.workers_for(scan_path);
+    let reuse_hashes = !options.full_rescan;
+    let inspect_archives = options.inspect_archives;
+    let walk_root = root.clone();
+    let walk_monitor = monitor.clone();
+    let index = index.cloned();
+    let (walk, search, archives, near_duplicates, junk, changes) = run_blocking(move || {
+        let monitor = walk_monitor;
+        let (previous_scan, previous) = match index.as_ref().map(|index| index.load(&walk_root)) {
+            Some(Ok(loaded)) => loaded,
+            Some(Err(e)) => {
+                eprintln!("Failed to load file index, rehashing everything: {}", e);
+                (None, HashMap::new())
             }
+            None => (None, HashMap::new()),
+        };
+
+        let mut walk = collect_files(
+            Path::new(&walk_root),
+            &rules,
+            &previous,
+            reuse_hashes,
+            workers,
+            &monitor,
+        );
+        let search = find_duplicates(&mut walk.files, workers, &monitor);
+        let archives = if inspect_archives {
+            find_extracted_archives(&mut walk.files, workers, &monitor)
+        } else {
+            Vec::new()
+        };
+        let mut near_duplicates = similar_images
+            .map(|options| find_similar_images(&walk.files, &options, workers, &monitor))
+            .unwrap_or_default();
+        if let Some(options) = similar_text {
+            near_duplicates.extend(find_similar_text(&walk.files, &options, workers, &monitor));
         }
-    }
+        let junk = find_structural_junk(
+            Path::new(&walk_root),
+            &walk.directories,
+            &walk.files,
+            std::mem::take(&mut walk.broken_symlinks),
+        );
+
+        // A partial walk would look like mass deletion, so leave the index alone
+        let changes = index.filter(|_| !monitor.is_cancelled()).map(|index| {
+            // Remember every hash computed this scan
+            for file in &walk.files {
+                if let Some(entry) = walk.index.get_mut(&file.path) {
+                    entry.hash = file.hash.clone();
+                }
+            }
+
+            let mut changes = ScanChanges::between(previous_scan, &previous, &walk.index);
+            changes.files_hashed = search.files_hashed;
+            changes.hashes_reused = walk.hashes_reused;
+
+            if let Err(e) = index.store(&walk_root, Utc::now(), &walk.index) {
+                eprintln!("Failed to update file index: {}", e);
+            }
+            changes
+        });
+        (walk, search, archives, near_duplicates, junk, changes)
+    })
+    .await?;
+    monitor.set_phase(ScanPhase::Analyzing);
+    let incomplete = monitor.is_cancelled();
 
     // Analyze results
-    let stats = analyze_folder_contents(all_files, file_hashes, total_directories);
+    let tree_files = options.tree.as_ref().map(|_| walk.files.clone());
+    let mut stats = analyze_folder_contents(walk.files, search.groups, walk.total_directories);
+    stats.hardlinks = walk.hardlinks;
+    stats.near_duplicates = near_duplicates;
+    stats.archives = archives;
+    stats.structural_junk = junk;
+    stats.changes = changes;
+    stats.incomplete = incomplete;
+
+    // Unreadable paths, so a scan that missed part of the tree doesn't look complete
+    let mut errors = walk.errors;
+    errors.extend(search.errors);
+    stats.error_counts = ErrorCounts::tally(&errors);
+    errors.truncate(MAX_LISTED_ERRORS);
+    stats.errors = errors;
+
+    if let (Some(tree_options), Some(files)) = (&options.tree, tree_files) {
+        let tree = build_tree(Path::new(&root), &files, &stats);
+        stats.tree = Some(tree.view(tree_options));
+        if let Some(trees) = trees {
+            trees.store(root, tree)?;
+        }
+    }
     Ok(stats)
 }
 
-#[command]
+#[cfg_attr(feature = "desktop", command)]
 pub async fn get_folder_stats(path: String) -> Result<serde_json::Value, String> {
     let scan_path = Path::new(&path);
 
@@ -129,68 +403,390 @@ pub async fn get_folder_stats(path: String) -> Result<serde_json::Value, String>
     Ok(stats)
 }
 
+#[cfg(feature = "desktop")]
 #[command]
-pub async fn detect_duplicates(paths: Vec<String>) -> Result<Vec<DuplicateGroup>, String> {
-    let mut file_hashes: HashMap<String, Vec<LocalFile>> = HashMap::new();
-
-    for path in paths {
-        let scan_path = Path::new(&path);
-
-        for entry in WalkDir::new(scan_path)
-            .into_iter()
-            .filter_entry(|e| !is_skip_directory(e))
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-
-            if path.is_file() {
-                if let Some(file_info) = process_file(&entry) {
-                    if file_info.size < 100 * 1024 * 1024 {
-                        // 100MB limit
-                        if let Ok(hash) = calculate_file_hash(path) {
-                            let mut file_with_hash = file_info;
-                            file_with_hash.hash = Some(hash.clone());
-                            file_hashes
-                                .entry(hash)
-                                .or_insert_with(Vec::new)
-                                .push(file_with_hash);
-                        }
-                    }
-                }
-            }
+pub async fn detect_duplicates(
+    app: AppHandle,
+    paths: Vec<String>,
+    rules: Option<ScanRules>,
+    workers: Option<WorkerConfig>,
+    scan_id: Option<String>,
+    scans: State<'_, ActiveScans>,
+) -> Result<DuplicateScanResult, String> {
+    let monitor = scans.start(scan_id, Some(event_sink(app)))?;
+    let result = scan_duplicates(
+        paths,
+        &rules.unwrap_or_default(),
+        workers.unwrap_or_default(),
+        &monitor,
+    )
+    .await;
+    scans.finish(&monitor);
+    result
+}
+
+/// Finds duplicate files across every tree in `paths`
+pub async fn scan_duplicates(
+    paths: Vec<String>,
+    rules: &ScanRules,
+    config: WorkerConfig,
+    monitor: &ScanMonitor,
+) -> Result<DuplicateScanResult, String> {
+    let matchers = paths
+        .iter()
+        .map(|path| rules.compile(Path::new(path)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let monitor = monitor.clone();
+
+    run_blocking(move || {
+        let mut all_files: Vec<LocalFile> = Vec::new();
+        let mut ids: HashMap<String, FileId> = HashMap::new();
+        let mut workers = 1;
+
+        for (path, rules) in paths.iter().zip(&matchers) {
+            let path_workers = config.workers_for(Path::new(path));
+            workers = workers.max(path_workers);
+            let walk = collect_files(
+                Path::new(path),
+                rules,
+                &HashMap::new(),
+                false,
+                path_workers,
+                &monitor,
+            );
+            all_files.extend(walk.files);
+            ids.extend(walk.ids);
         }
+
+        // Overlapping paths would otherwise report a file as its own duplicate
+        all_files.sort_by(|a, b| a.path.cmp(&b.path));
+        all_files.dedup_by(|a, b| a.path == b.path);
+        collapse_links(&mut all_files, &ids);
+
+        DuplicateScanResult {
+            groups: find_duplicates(&mut all_files, workers, &monitor).groups,
+            incomplete: monitor.is_cancelled(),
+        }
+    })
+    .await
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn scan_build_artifacts(
+    app: AppHandle,
+    path: String,
+    workers: Option<WorkerConfig>,
+    scan_id: Option<String>,
+    scans: State<'_, ActiveScans>,
+) -> Result<ArtifactScanResult, String> {
+    let monitor = scans.start(scan_id, Some(event_sink(app)))?;
+    let result = scan_artifacts(&path, workers.unwrap_or_default(), &monitor).await;
+    scans.finish(&monitor);
+    result
+}
+
+/// Finds node_modules, target, build and dist directories under `path`,
+/// including the ones a regular scan skips
+pub async fn scan_artifacts(
+    path: &str,
+    config: WorkerConfig,
+    monitor: &ScanMonitor,
+) -> Result<ArtifactScanResult, String> {
+    let scan_path = Path::new(path);
+    if !scan_path.is_dir() {
+        return Err(format!("{} is not a directory", path));
     }
 
-    // Find duplicates
-    let duplicates: Vec<DuplicateGroup> = file_hashes
-        .into_iter()
-        .filter(|(_, files)| files.len() > 1)
-        .map(|(hash, files)| {
-            let total_size = files[0].size * files.len() as u64;
-            DuplicateGroup {
-                hash,
-                total_size,
-                files,
+    let root =
+        fs::canonicalize(scan_path).map_err(|e| format!("Failed to resolve {}: {}", path, e))?;
+    let workers = config.workers_for(&root);
+    let monitor = monitor.clone();
+
+    run_blocking(move || find_build_artifacts(&root, workers, &monitor)).await
+}
+
+/// Runs filesystem-heavy work off the async runtime
+async fn run_blocking<T, F>(work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| format!("Scan worker failed: {}", e))
+}
+
+struct WalkResult {
+    files: Vec<LocalFile>,
+    errors: Vec<ScanError>,
+    total_directories: usize,
+    directories: Vec<PathBuf>,
+    broken_symlinks: Vec<BrokenSymlink>,
+    index: HashMap<String, IndexEntry>,
+    hashes_reused: usize,
+    ids: HashMap<String, FileId>,
+    hardlinks: Vec<HardlinkSet>,
+}
+
+// What a walker thread found
+enum Walked {
+    Directory(PathBuf),
+    File(Box<(LocalFile, IndexEntry, Option<FileId>)>),
+    BrokenLink(BrokenSymlink),
+    Error(ScanError),
+}
+
+/// Where a file lives on disk, shared by every path that leads to it
+#[derive(Clone, Copy)]
+pub(crate) struct FileId {
+    pub(crate) device: u64,
+    pub(crate) inode: u64,
+    pub(crate) links: u64,
+}
+
+/// Walks `scan_path` on `workers` threads and returns every file `rules`
+/// keeps, sorted by path. A file reached through several paths is listed
+/// once, under its first path, with the rest in `hardlinks`. Hashes and
+/// categories from `previous` are carried over for unchanged files when
+/// `reuse_hashes` is set. Stops at the next entry once `monitor` is cancelled.
+fn collect_files(
+    scan_path: &Path,
+    rules: &RuleMatcher,
+    previous: &HashMap<String, IndexEntry>,
+    reuse_hashes: bool,
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> WalkResult {
+    let mut walk = WalkResult {
+        files: Vec::new(),
+        errors: Vec::new(),
+        total_directories: 0,
+        directories: Vec::new(),
+        broken_symlinks: Vec::new(),
+        index: HashMap::new(),
+        hashes_reused: 0,
+        ids: HashMap::new(),
+        hardlinks: Vec::new(),
+    };
+
+    monitor.set_phase(ScanPhase::Walking);
+    let known = reuse_hashes.then_some(previous);
+    let (tx, rx) = mpsc::channel();
+    let mut builder = WalkBuilder::new(scan_path);
+    rules.configure(&mut builder);
+    builder.threads(workers).build_parallel().run(|| {
+        let tx = tx.clone();
+        Box::new(move |entry| {
+            if monitor.is_cancelled() {
+                return WalkState::Quit;
+            }
+            let found = match entry {
+                Ok(entry) => walked_entry(&entry, rules, known, monitor),
+                // Following a link whose target is gone fails the walk of that entry
+                Err(e) => error_path(&e)
+                    .and_then(broken_symlink)
+                    .map(Walked::BrokenLink)
+                    .or_else(|| ScanError::from_walk(&e).map(Walked::Error)),
+            };
+            match found {
+                Some(found) => match tx.send(found) {
+                    Ok(()) => WalkState::Continue,
+                    Err(_) => WalkState::Quit,
+                },
+                None => WalkState::Continue,
             }
         })
-        .collect();
+    });
+    drop(tx);
+
+    for found in rx {
+        let (mut file_info, index_entry, id) = match found {
+            Walked::File(walked) => *walked,
+            Walked::Directory(path) => {
+                walk.total_directories += 1;
+                walk.directories.push(path);
+                continue;
+            }
+            Walked::BrokenLink(link) => {
+                walk.broken_symlinks.push(link);
+                continue;
+            }
+            Walked::Error(error) => {
+                walk.errors.push(error);
+                continue;
+            }
+        };
+
+        // Reuse the hash of an unchanged file; duplicate detection hashes the rest on demand
+        if let Some(old) = previous.get(&file_info.path) {
+            if reuse_hashes && old.hash.is_some() && old.is_unchanged(&index_entry) {
+                file_info.hash = old.hash.clone();
+                walk.hashes_reused += 1;
+            }
+        }
 
-    Ok(duplicates)
+        walk.index.insert(file_info.path.clone(), index_entry);
+        if let Some(id) = id {
+            walk.ids.insert(file_info.path.clone(), id);
+        }
+        walk.files.push(file_info);
+    }
+
+    // Workers finish in any order, so keep results stable between scans
+    walk.files.sort_by(|a, b| a.path.cmp(&b.path));
+    walk.broken_symlinks.sort_by(|a, b| a.path.cmp(&b.path));
+    walk.errors.sort_by(|a, b| a.path.cmp(&b.path));
+    walk.hardlinks = collapse_links(&mut walk.files, &walk.ids);
+    walk
 }
 
-fn is_skip_directory(entry: &DirEntry) -> bool {
-    entry
-        .file_name()
+/// Keeps the first path to each file in `files` and returns the paths that
+/// lead to a file already listed, grouped per file
+fn collapse_links(files: &mut Vec<LocalFile>, ids: &HashMap<String, FileId>) -> Vec<HardlinkSet> {
+    let mut sets: Vec<HardlinkSet> = Vec::new();
+    let mut seen: HashMap<(u64, u64), usize> = HashMap::new();
+
+    files.retain(|file| {
+        let Some(id) = ids.get(&file.path) else {
+            return true;
+        };
+        match seen.get(&(id.device, id.inode)) {
+            Some(&set) => {
+                sets[set].paths.push(file.path.clone());
+                false
+            }
+            None => {
+                seen.insert((id.device, id.inode), sets.len());
+                sets.push(HardlinkSet {
+                    device: id.device,
+                    inode: id.inode,
+                    size: file.size,
+                    link_count: id.links,
+                    paths: vec![file.path.clone()],
+                });
+                true
+            }
+        }
+    });
+
+    sets.retain(|set| set.paths.len() > 1);
+    sets
+}
+
+fn walked_entry(
+    entry: &ignore::DirEntry,
+    rules: &RuleMatcher,
+    known: Option<&HashMap<String, IndexEntry>>,
+    monitor: &ScanMonitor,
+) -> Option<Walked> {
+    match entry.file_type() {
+        Some(t) if t.is_dir() => Some(Walked::Directory(entry.path().to_path_buf())),
+        Some(t) if t.is_file() => {
+            monitor.file_seen(entry.path());
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    let error = match e.io_error() {
+                        Some(io_error) => ScanError::from_io(entry.path(), io_error),
+                        None => ScanError::from_walk(&e)?,
+                    };
+                    return Some(Walked::Error(error));
+                }
+            };
+            if !rules.keeps_file(entry.path(), metadata.len()) {
+                return None;
+            }
+            Some(match walked_file(entry.path(), &metadata, known) {
+                Ok(walked) => Walked::File(Box::new(walked)),
+                Err(error) => Walked::Error(error),
+            })
+        }
+        Some(t) if t.is_symlink() => broken_symlink(entry.path()).map(Walked::BrokenLink),
+        _ => None,
+    }
+}
+
+fn walked_file(
+    path: &Path,
+    metadata: &fs::Metadata,
+    known: Option<&HashMap<String, IndexEntry>>,
+) -> Result<(LocalFile, IndexEntry, Option<FileId>), ScanError> {
+    let mut index_entry = IndexEntry {
+        size: metadata.len(),
+        modified_ns: modified_ns(metadata),
+        inode: file_inode(metadata),
+        hash: None,
+        category: None,
+    };
+    // An unchanged file keeps the category sniffed last time, so it isn't read again
+    let category = path
         .to_str()
-        .map(|s| s.starts_with('.') || SKIP_DIRECTORIES.contains(&s))
-        .unwrap_or(false)
+        .and_then(|p| known?.get(p))
+        .filter(|old| old.is_unchanged(&index_entry))
+        .and_then(|old| old.category.as_deref());
+    let file_info = process_file(path, metadata, category)?;
+    index_entry.category = Some(file_info.category.clone());
+    Ok((file_info, index_entry, file_id(metadata)))
+}
+
+fn modified_ns(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
 }
 
-fn process_file(entry: &DirEntry) -> Option<LocalFile> {
-    let path = entry.path();
-    let metadata = entry.metadata().ok()?;
+#[cfg(unix)]
+fn file_inode(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn file_inode(_metadata: &fs::Metadata) -> u64 {
+    0
+}
 
-    let name = path.file_name()?.to_str()?.to_string();
+#[cfg(unix)]
+pub(crate) fn file_id(metadata: &fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileId {
+        device: metadata.dev(),
+        inode: metadata.ino(),
+        links: metadata.nlink(),
+    })
+}
+
+#[cfg(not(unix))]
+pub(crate) fn file_id(_metadata: &fs::Metadata) -> Option<FileId> {
+    None
+}
+
+// st_blocks is always in 512-byte units, whatever the filesystem block size
+#[cfg(unix)]
+pub(crate) fn allocated_size(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.blocks() * 512)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn allocated_size(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+fn process_file(
+    path: &Path,
+    metadata: &fs::Metadata,
+    category: Option<&str>,
+) -> Result<LocalFile, ScanError> {
+    let (Some(name), Some(full_path)) = (path.file_name().and_then(|n| n.to_str()), path.to_str())
+    else {
+        return Err(ScanError::non_utf8_name(path));
+    };
+    let name = name.to_string();
     let extension = path
         .extension()
         .and_then(|e| e.to_str())
@@ -206,9 +802,12 @@ fn process_file(entry: &DirEntry) -> Option<LocalFile> {
     let accessed_time = metadata.accessed().ok().map(|t| DateTime::<Utc>::from(t));
 
     let is_hidden = name.starts_with('.');
+    let category = category
+        .unwrap_or_else(|| detect_category(path, &extension))
+        .to_string();
 
-    Some(LocalFile {
-        path: path.to_str()?.to_string(),
+    Ok(LocalFile {
+        path: full_path.to_string(),
         name,
         extension,
         size: metadata.len(),
@@ -216,37 +815,27 @@ fn process_file(entry: &DirEntry) -> Option<LocalFile> {
         accessed_time,
         is_hidden,
         hash: None,
+        allocated_size: allocated_size(metadata),
+        category,
     })
 }
 
-fn calculate_file_hash(path: &Path) -> Result<String, std::io::Error> {
-    let mut file = fs::File::open(path)?;
-    let mut hasher = Sha256::new();
-    let mut buffer = vec![0; 8192];
-
-    loop {
-        let bytes_read = file.read(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-        hasher.update(&buffer[..bytes_read]);
-    }
-
-    Ok(format!("{:x}", hasher.finalize()))
-}
-
 fn analyze_folder_contents(
     files: Vec<LocalFile>,
-    file_hashes: HashMap<String, Vec<LocalFile>>,
+    duplicates: Vec<DuplicateGroup>,
     total_directories: usize,
 ) -> FolderStats {
     let mut total_size = 0u64;
+    let mut disk_usage = 0u64;
     let mut file_types: HashMap<String, usize> = HashMap::new();
+    let mut extensions: HashMap<String, usize> = HashMap::new();
 
     // Calculate totals
     for file in &files {
         total_size += file.size;
-        *file_types.entry(file.extension.clone()).or_insert(0) += 1;
+        disk_usage += file.allocated_size.unwrap_or(file.size);
+        *file_types.entry(file.category.clone()).or_insert(0) += 1;
+        *extensions.entry(file.extension.to_lowercase()).or_insert(0) += 1;
     }
 
     // Find largest files
@@ -258,30 +847,15 @@ fn analyze_folder_contents(
         .take(20)
         .collect();
 
-    // Find duplicates
-    let duplicates: Vec<DuplicateGroup> = file_hashes
-        .into_iter()
-        .filter(|(_, files)| files.len() > 1)
-        .map(|(hash, files)| {
-            let total_size = files[0].size * (files.len() - 1) as u64; // Wasted space
-            DuplicateGroup {
-                hash,
-                total_size,
-                files,
-            }
-        })
-        .collect();
+    let temporary = TempClassifier::new().summarize(&files);
+    let versioned = local_version_clusters(&files);
 
     // Find unused files (not accessed in 6+ months)
-    let cutoff_date = Utc::now() - chrono::Duration::days(UNUSED_DAYS_THRESHOLD);
+    let cutoff_date = unused_cutoff();
     let total_files_count = files.len();
     let unused_files: Vec<LocalFile> = files
         .into_iter()
-        .filter(|f| {
-            f.accessed_time
-                .map(|accessed| accessed < cutoff_date)
-                .unwrap_or(false)
-        })
+        .filter(|f| is_unused(f, cutoff_date))
         .take(50)
         .collect();
 
@@ -289,10 +863,51 @@ fn analyze_folder_contents(
         total_files: total_files_count,
         total_directories,
         total_size,
+        disk_usage,
         file_types,
+        extensions,
         largest_files,
         duplicates,
+        near_duplicates: Vec::new(),
+        archives: Vec::new(),
         unused_files,
+        hardlinks: Vec::new(),
+        temporary,
+        versioned,
+        structural_junk: StructuralJunk::default(),
+        tree: None,
         scan_timestamp: Utc::now(),
+        changes: None,
+        incomplete: false,
+        errors: Vec::new(),
+        error_counts: ErrorCounts::default(),
     }
 }
+
+/// Files last accessed before this count as unused
+pub(crate) fn unused_cutoff() -> DateTime<Utc> {
+    Utc::now() - chrono::Duration::days(UNUSED_DAYS_THRESHOLD)
+}
+
+pub(crate) fn is_unused(file: &LocalFile, cutoff: DateTime<Utc>) -> bool {
+    file.accessed_time
+        .map(|accessed| accessed < cutoff)
+        .unwrap_or(false)
+}
+
+/// Indices of the files `wanted` keeps for a near-duplicate search. Byte-identical
+/// copies are already reported as duplicates, so only one file per hash is read.
+pub(crate) fn one_per_hash(files: &[LocalFile], wanted: impl Fn(&LocalFile) -> bool) -> Vec<usize> {
+    let mut seen_hashes = HashSet::new();
+    files
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| wanted(f))
+        .filter(|(_, f)| {
+            f.hash
+                .as_ref()
+                .is_none_or(|h| seen_hashes.insert(h.clone()))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
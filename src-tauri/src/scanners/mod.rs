@@ -1,4 +1,22 @@
 // src-tauri/src/scanners/mod.rs
+pub mod archives;
+pub mod build_artifacts;
 pub mod drive_scanner;
+pub mod duplicates;
+pub mod file_index;
+pub mod file_types;
 pub mod github_scanner;
+pub mod junk;
 pub mod local_scanner;
+pub mod progress;
+pub mod rules;
+pub mod scan_errors;
+pub mod scanner;
+pub mod similar_images;
+pub mod similar_text;
+pub mod size_tree;
+pub mod temp_files;
+pub mod versions;
+pub mod workers;
+
+pub use scanner::{ItemSource, ScannedItem, Scanner, ScannerCapabilities};
@@ -0,0 +1,277 @@
+// src-tauri/src/scanners/progress.rs
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+#[cfg(feature = "desktop")]
+use tauri::{command, AppHandle, Emitter, State};
+
+pub const SCAN_PROGRESS_EVENT: &str = "scan-progress";
+
+// Progress is reported at most this often, plus on every phase change
+const REPORT_INTERVAL: Duration = Duration::from_millis(100);
+
+static NEXT_SCAN_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPhase {
+    Walking,
+    Hashing,
+    Fetching,
+    Analyzing,
+    Done,
+}
+
+/// Payload of the `scan-progress` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanProgress {
+    pub scan_id: String,
+    pub phase: ScanPhase,
+    pub files_seen: u64,
+    pub bytes_hashed: u64,
+    pub bytes_to_hash: u64, // Grows as duplicate detection finds more candidates
+    pub repos_fetched: u64,
+    pub current_path: Option<String>, // Or repository name for GitHub scans
+    pub elapsed_seconds: f64,
+    pub eta_seconds: Option<f64>, // Only known while hashing
+    pub cancelled: bool,
+}
+
+pub type ProgressSink = Arc<dyn Fn(&ScanProgress) + Send + Sync>;
+
+/// Shared by every worker of one scan: counts progress, reports it to an
+/// optional sink and carries the scan's cancellation flag
+#[derive(Clone)]
+pub struct ScanMonitor {
+    inner: Arc<MonitorState>,
+}
+
+struct MonitorState {
+    scan_id: String,
+    sink: Option<ProgressSink>,
+    cancelled: AtomicBool,
+    files_seen: AtomicU64,
+    bytes_hashed: AtomicU64,
+    bytes_to_hash: AtomicU64,
+    repos_fetched: AtomicU64,
+    started: Instant,
+    last_report_ms: AtomicU64, // Since `started`, so workers can skip the lock between reports
+    status: Mutex<MonitorStatus>,
+}
+
+struct MonitorStatus {
+    phase: ScanPhase,
+    phase_started: Instant,
+    current_path: Option<String>,
+}
+
+impl ScanMonitor {
+    pub fn new(scan_id: impl Into<String>, sink: Option<ProgressSink>) -> Self {
+        let now = Instant::now();
+        ScanMonitor {
+            inner: Arc::new(MonitorState {
+                scan_id: scan_id.into(),
+                sink,
+                cancelled: AtomicBool::new(false),
+                files_seen: AtomicU64::new(0),
+                bytes_hashed: AtomicU64::new(0),
+                bytes_to_hash: AtomicU64::new(0),
+                repos_fetched: AtomicU64::new(0),
+                started: now,
+                last_report_ms: AtomicU64::new(0),
+                status: Mutex::new(MonitorStatus {
+                    phase: ScanPhase::Walking,
+                    phase_started: now,
+                    current_path: None,
+                }),
+            }),
+        }
+    }
+
+    /// A monitor nobody watches or cancels
+    pub fn detached() -> Self {
+        Self::new("detached", None)
+    }
+
+    pub fn scan_id(&self) -> &str {
+        &self.inner.scan_id
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        self.report(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_phase(&self, phase: ScanPhase) {
+        if let Ok(mut status) = self.inner.status.lock() {
+            status.phase = phase;
+            status.phase_started = Instant::now();
+        }
+        self.report(true);
+    }
+
+    pub fn file_seen(&self, path: &Path) {
+        self.inner.files_seen.fetch_add(1, Ordering::Relaxed);
+        self.touch(|| path.to_string_lossy().to_string());
+    }
+
+    pub fn will_hash(&self, bytes: u64) {
+        self.inner.bytes_to_hash.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn bytes_hashed(&self, bytes: u64, path: &Path) {
+        self.inner.bytes_hashed.fetch_add(bytes, Ordering::Relaxed);
+        self.touch(|| path.to_string_lossy().to_string());
+    }
+
+    pub fn repos_fetched(&self, repos: u64, name: &str) {
+        self.inner.repos_fetched.fetch_add(repos, Ordering::Relaxed);
+        self.touch(|| name.to_string());
+    }
+
+    /// Marks the current GitHub repository without counting it again
+    pub fn checking_repo(&self, name: &str) {
+        self.touch(|| name.to_string());
+    }
+
+    pub fn snapshot(&self) -> ScanProgress {
+        let state = &self.inner;
+        let (phase, phase_elapsed, current_path) = match state.status.lock() {
+            Ok(status) => (
+                status.phase,
+                status.phase_started.elapsed().as_secs_f64(),
+                status.current_path.clone(),
+            ),
+            Err(_) => (ScanPhase::Walking, 0.0, None),
+        };
+
+        let bytes_hashed = state.bytes_hashed.load(Ordering::Relaxed);
+        let bytes_to_hash = state.bytes_to_hash.load(Ordering::Relaxed);
+        let eta_seconds = (phase == ScanPhase::Hashing && bytes_hashed > 0).then(|| {
+            let remaining = bytes_to_hash.saturating_sub(bytes_hashed) as f64;
+            phase_elapsed * remaining / bytes_hashed as f64
+        });
+
+        ScanProgress {
+            scan_id: state.scan_id.clone(),
+            phase,
+            files_seen: state.files_seen.load(Ordering::Relaxed),
+            bytes_hashed,
+            bytes_to_hash,
+            repos_fetched: state.repos_fetched.load(Ordering::Relaxed),
+            current_path,
+            elapsed_seconds: state.started.elapsed().as_secs_f64(),
+            eta_seconds,
+            cancelled: self.is_cancelled(),
+        }
+    }
+
+    /// Records what is being worked on, but only when a report is due
+    fn touch(&self, current: impl FnOnce() -> String) {
+        if self.inner.sink.is_none() || !self.claim_report() {
+            return;
+        }
+        if let Ok(mut status) = self.inner.status.lock() {
+            status.current_path = Some(current());
+        }
+        self.send();
+    }
+
+    /// Sends a snapshot to the sink, throttled unless `force` is set
+    fn report(&self, force: bool) {
+        if force || self.claim_report() {
+            self.send();
+        }
+    }
+
+    fn claim_report(&self) -> bool {
+        let now = self.inner.started.elapsed().as_millis() as u64;
+        let last = self.inner.last_report_ms.load(Ordering::Relaxed);
+        now.saturating_sub(last) >= REPORT_INTERVAL.as_millis() as u64
+            && self
+                .inner
+                .last_report_ms
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+    }
+
+    fn send(&self) {
+        if let Some(sink) = &self.inner.sink {
+            sink(&self.snapshot());
+        }
+    }
+}
+
+/// Scans currently running, so they can be cancelled by id
+#[derive(Default)]
+pub struct ActiveScans {
+    scans: Mutex<HashMap<String, ScanMonitor>>,
+}
+
+impl ActiveScans {
+    /// Registers a scan under `scan_id`, or a generated id when none is given
+    pub fn start(
+        &self,
+        scan_id: Option<String>,
+        sink: Option<ProgressSink>,
+    ) -> Result<ScanMonitor, String> {
+        let scan_id = scan_id
+            .unwrap_or_else(|| format!("scan-{}", NEXT_SCAN_ID.fetch_add(1, Ordering::Relaxed)));
+        let mut scans = self.lock()?;
+        if scans.contains_key(&scan_id) {
+            return Err(format!("Scan {} is already running", scan_id));
+        }
+
+        let monitor = ScanMonitor::new(scan_id.clone(), sink);
+        scans.insert(scan_id, monitor.clone());
+        Ok(monitor)
+    }
+
+    pub fn finish(&self, monitor: &ScanMonitor) {
+        monitor.set_phase(ScanPhase::Done);
+        if let Ok(mut scans) = self.lock() {
+            scans.remove(monitor.scan_id());
+        }
+    }
+
+    /// Asks a running scan to stop. Returns false if no such scan is running.
+    pub fn cancel(&self, scan_id: &str) -> Result<bool, String> {
+        let scans = self.lock()?;
+        match scans.get(scan_id) {
+            Some(monitor) => {
+                monitor.cancel();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, ScanMonitor>>, String> {
+        self.scans
+            .lock()
+            .map_err(|_| "Scan registry is unavailable".to_string())
+    }
+}
+
+/// Forwards progress to the frontend as `scan-progress` events
+#[cfg(feature = "desktop")]
+pub fn event_sink(app: AppHandle) -> ProgressSink {
+    Arc::new(move |progress| {
+        if let Err(e) = app.emit(SCAN_PROGRESS_EVENT, progress) {
+            eprintln!("Failed to emit scan progress: {}", e);
+        }
+    })
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn cancel_scan(scan_id: String, scans: State<'_, ActiveScans>) -> Result<bool, String> {
+    scans.cancel(&scan_id)
+}
@@ -0,0 +1,166 @@
+// src-tauri/src/scanners/rules.rs
+use super::temp_files::{self, TempClassifier};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+// Dependency, build and system folders skipped unless a profile says otherwise
+const DEFAULT_EXCLUDES: &[&str] = &[
+    "node_modules/",
+    ".git/",
+    ".vscode/",
+    "target/",
+    "build/",
+    "dist/",
+    "$RECYCLE.BIN/",
+    "System Volume Information/",
+    "Windows/",
+    "Program Files/",
+    "Program Files (x86)/",
+    "ProgramData/",
+];
+
+/// Which files a local scan looks at. Patterns use gitignore syntax and are
+/// matched relative to the scanned directory.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScanRules {
+    pub include: Vec<String>, // When set, only files matching one of these are kept
+    pub exclude: Vec<String>, // Files and directories to skip entirely
+    pub respect_ignore_files: bool, // Honor .gitignore and .ignore files found while walking
+    pub max_depth: Option<usize>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub include_hidden: bool,
+    pub follow_symlinks: bool, // Walk into symlinked files and folders instead of skipping them
+    pub cross_filesystems: bool, // Descend into other mounted filesystems below the root
+}
+
+impl Default for ScanRules {
+    fn default() -> Self {
+        ScanRules {
+            include: Vec::new(),
+            exclude: DEFAULT_EXCLUDES.iter().map(|p| p.to_string()).collect(),
+            respect_ignore_files: false,
+            max_depth: None,
+            min_size: None,
+            max_size: None,
+            include_hidden: false,
+            follow_symlinks: false,
+            cross_filesystems: false,
+        }
+    }
+}
+
+impl ScanRules {
+    /// Checks the patterns and prepares them for a walk of `root`
+    pub fn compile(&self, root: &Path) -> Result<RuleMatcher, String> {
+        if let (Some(min), Some(max)) = (self.min_size, self.max_size) {
+            if min > max {
+                return Err(format!(
+                    "Minimum file size ({} bytes) is larger than the maximum ({} bytes)",
+                    min, max
+                ));
+            }
+        }
+
+        Ok(RuleMatcher {
+            rules: self.clone(),
+            root: root.to_path_buf(),
+            temp: TempClassifier::new(),
+            include: build_matcher(root, &self.include, "include")?,
+            exclude: build_matcher(root, &self.exclude, "exclude")?,
+        })
+    }
+}
+
+fn build_matcher(root: &Path, patterns: &[String], kind: &str) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| format!("Invalid {} pattern '{}': {}", kind, pattern, e))?;
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Invalid {} patterns: {}", kind, e))
+}
+
+/// `ScanRules` compiled against one scan root
+#[derive(Clone)]
+pub struct RuleMatcher {
+    rules: ScanRules,
+    root: PathBuf,
+    temp: TempClassifier,
+    include: Gitignore,
+    exclude: Gitignore,
+}
+
+impl RuleMatcher {
+    /// Applies the depth, hidden-file, ignore-file, symlink and filesystem
+    /// settings to a walk. Without `include_hidden`, dot-directories that hold
+    /// caches and hidden temp files are still walked so temp detection sees them.
+    pub fn configure(&self, builder: &mut WalkBuilder) {
+        let ignore_files = self.rules.respect_ignore_files;
+        builder
+            .standard_filters(false)
+            .hidden(false)
+            .git_ignore(ignore_files)
+            .git_exclude(ignore_files)
+            .ignore(ignore_files)
+            .parents(ignore_files)
+            .require_git(false)
+            .follow_links(self.rules.follow_symlinks)
+            .same_file_system(!self.rules.cross_filesystems)
+            .max_depth(self.rules.max_depth);
+
+        let exclude = self.exclude.clone();
+        let include_hidden = self.rules.include_hidden;
+        let temp = self.temp.clone();
+        builder.filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            if exclude.matched(entry.path(), is_dir).is_ignore() {
+                return false;
+            }
+            if include_hidden || entry.depth() == 0 {
+                return true;
+            }
+            match entry.file_name().to_str() {
+                Some(name) if name.starts_with('.') => {
+                    if is_dir {
+                        temp_files::may_hold_temp_files(name)
+                    } else {
+                        temp.classify(entry.path()).is_some()
+                    }
+                }
+                _ => true,
+            }
+        });
+    }
+
+    /// Whether a walked file passes the include patterns and size limits
+    pub fn keeps_file(&self, path: &Path, size: u64) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .matched_path_or_any_parents(path, false)
+                .is_ignore();
+
+        included
+            && (self.rules.include_hidden
+                || !self.is_hidden(path)
+                || self.temp.classify(path).is_some())
+            && self.rules.min_size.is_none_or(|min| size >= min)
+            && self.rules.max_size.is_none_or(|max| size <= max)
+    }
+
+    // Files inside a walked dot-directory are only kept when they are temp files
+    fn is_hidden(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        relative.components().any(|c| match c {
+            Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+            _ => false,
+        })
+    }
+}
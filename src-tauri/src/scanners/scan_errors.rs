@@ -0,0 +1,100 @@
+// src-tauri/src/scanners/scan_errors.rs
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+// Errors listed in a scan result; the counts cover every one
+pub const MAX_LISTED_ERRORS: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanErrorKind {
+    Permission,
+    Io,
+    TooLong,     // The path exceeds what the OS allows
+    NonUtf8Name, // Readable, but the name can't be represented in the results
+}
+
+/// A path the scan could not read, so nothing below it was counted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanError {
+    pub path: String,
+    pub kind: ScanErrorKind,
+    pub message: String,
+}
+
+impl ScanError {
+    pub fn from_io(path: &Path, error: &io::Error) -> Self {
+        let kind = match error.kind() {
+            io::ErrorKind::PermissionDenied => ScanErrorKind::Permission,
+            io::ErrorKind::InvalidFilename => ScanErrorKind::TooLong,
+            _ => ScanErrorKind::Io,
+        };
+        ScanError {
+            path: path.to_string_lossy().to_string(),
+            kind,
+            message: error.to_string(),
+        }
+    }
+
+    /// Errors from the directory walker; None for ones not tied to a path,
+    /// such as a malformed .gitignore line
+    pub fn from_walk(error: &ignore::Error) -> Option<Self> {
+        let path = error_path(error)?;
+        Some(match error.io_error() {
+            Some(io_error) => ScanError::from_io(path, io_error),
+            None => ScanError {
+                path: path.to_string_lossy().to_string(),
+                kind: ScanErrorKind::Io,
+                message: error.to_string(),
+            },
+        })
+    }
+
+    pub fn non_utf8_name(path: &Path) -> Self {
+        ScanError {
+            path: path.to_string_lossy().to_string(),
+            kind: ScanErrorKind::NonUtf8Name,
+            message: "File name is not valid UTF-8".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ErrorCounts {
+    pub permission: usize,
+    pub io: usize,
+    pub too_long: usize,
+    pub non_utf8_name: usize,
+}
+
+impl ErrorCounts {
+    pub fn tally(errors: &[ScanError]) -> Self {
+        let mut counts = ErrorCounts::default();
+        for error in errors {
+            match error.kind {
+                ScanErrorKind::Permission => counts.permission += 1,
+                ScanErrorKind::Io => counts.io += 1,
+                ScanErrorKind::TooLong => counts.too_long += 1,
+                ScanErrorKind::NonUtf8Name => counts.non_utf8_name += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn total(&self) -> usize {
+        self.permission + self.io + self.too_long + self.non_utf8_name
+    }
+}
+
+/// The path an error from the walker is about, if it names one
+pub(crate) fn error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,77 @@
+// src-tauri/src/scanners/scanner.rs
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a scanned item came from. The label doubles as the burn score
+/// category name, so adding a source here is enough for it to be scored.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemSource {
+    Local,
+    GitHub,
+    Drive,
+}
+
+impl ItemSource {
+    pub const ALL: [ItemSource; 3] = [ItemSource::Local, ItemSource::GitHub, ItemSource::Drive];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemSource::Local => "Local Files",
+            ItemSource::GitHub => "GitHub",
+            ItemSource::Drive => "Google Drive",
+        }
+    }
+
+    /// Weight of this source in the overall burn score
+    pub fn score_weight(&self) -> f64 {
+        match self {
+            ItemSource::Local => 0.4,
+            ItemSource::Drive => 0.35,
+            ItemSource::GitHub => 0.25,
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<ItemSource> {
+        ItemSource::ALL.into_iter().find(|s| s.label() == label)
+    }
+}
+
+/// A file, repository or document normalized across every scanner
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScannedItem {
+    pub id: String,
+    pub source: ItemSource,
+    pub name: String,
+    pub location: String, // Local path or web URL
+    pub size_bytes: u64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub modified_at: Option<DateTime<Utc>>,
+    pub accessed_at: Option<DateTime<Utc>>,
+    pub fingerprint: Option<String>, // Content hash, when the source provides one
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ScannerCapabilities {
+    pub requires_auth: bool,
+    pub content_fingerprints: bool,
+    pub access_times: bool,
+}
+
+/// A source of `ScannedItem`s that the burn score engine can analyze
+#[async_trait]
+pub trait Scanner: Send + Sync {
+    fn source(&self) -> ItemSource;
+
+    fn name(&self) -> &'static str {
+        self.source().label()
+    }
+
+    fn capabilities(&self) -> ScannerCapabilities;
+
+    /// Stores and validates a credential. Scanners without auth accept anything.
+    async fn authenticate(&mut self, credential: &str) -> Result<(), String>;
+
+    async fn scan(&self) -> Result<Vec<ScannedItem>, String>;
+}
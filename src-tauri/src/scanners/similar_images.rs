@@ -0,0 +1,292 @@
+// src-tauri/src/scanners/similar_images.rs
+use super::local_scanner::{one_per_hash, LocalFile, NearDuplicateGroup, NearDuplicateKind};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::workers::WorkerPool;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageReader};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::path::Path;
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp", "tif", "tiff"];
+
+// pHash works on a 32x32 thumbnail and keeps the 8x8 lowest frequencies
+const DCT_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageHashAlgorithm {
+    Average,    // aHash: fastest, but brightness and contrast edits throw it off
+    Difference, // dHash: compares neighbouring pixels, good for resized copies
+    #[default]
+    Perceptual, // pHash: DCT based, also survives re-encoding and small edits
+}
+
+impl std::str::FromStr for ImageHashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average" | "ahash" => Ok(ImageHashAlgorithm::Average),
+            "difference" | "dhash" => Ok(ImageHashAlgorithm::Difference),
+            "perceptual" | "phash" => Ok(ImageHashAlgorithm::Perceptual),
+            _ => Err(format!("Unknown image hash algorithm: {}", s)),
+        }
+    }
+}
+
+/// Settings for the optional near-duplicate image pass
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ImageSimilarity {
+    pub algorithm: ImageHashAlgorithm,
+    pub threshold: f64, // Share of matching hash bits, 0 to 1, for two images to be grouped
+}
+
+impl Default for ImageSimilarity {
+    fn default() -> Self {
+        ImageSimilarity {
+            algorithm: ImageHashAlgorithm::default(),
+            threshold: 0.9,
+        }
+    }
+}
+
+impl ImageSimilarity {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(format!(
+                "Image similarity threshold must be between 0 and 1, got {}",
+                self.threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct HashedImage {
+    index: usize,
+    hash: u64,
+    pixels: u64,
+}
+
+/// Groups images that look alike but are not byte-identical, such as resized
+/// or re-encoded copies of one photo. Each group keeps the highest resolution
+/// image first. Decoding runs on `workers` threads and stops early if
+/// `monitor` is cancelled.
+pub fn find_similar_images(
+    files: &[LocalFile],
+    options: &ImageSimilarity,
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> Vec<NearDuplicateGroup> {
+    let pool = WorkerPool::new(workers, "image");
+    monitor.set_phase(ScanPhase::Hashing);
+
+    let candidates = one_per_hash(files, |f| f.size > 0 && is_image(&f.extension));
+    monitor.will_hash(candidates.iter().map(|&i| files[i].size).sum());
+
+    let mut images: Vec<HashedImage> = pool.filter_map(&candidates, |&i| {
+        if monitor.is_cancelled() {
+            return None;
+        }
+        let path = Path::new(&files[i].path);
+        let hashed = image_hash(path, options.algorithm);
+        monitor.bytes_hashed(files[i].size, path);
+        let (hash, pixels) = hashed.ok()?;
+        Some(HashedImage {
+            index: i,
+            hash,
+            pixels,
+        })
+    });
+    if monitor.is_cancelled() {
+        return Vec::new();
+    }
+
+    // Largest images first, so each group is led by its best copy
+    images.sort_by(|a, b| {
+        b.pixels
+            .cmp(&a.pixels)
+            .then(files[b.index].size.cmp(&files[a.index].size))
+            .then(a.index.cmp(&b.index))
+    });
+
+    let bands = bands(options.threshold);
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (position, image) in images.iter().enumerate() {
+        for (band, &(shift, width)) in bands.iter().enumerate() {
+            buckets
+                .entry((band, band_bits(image.hash, shift, width)))
+                .or_default()
+                .push(position);
+        }
+    }
+
+    let mut grouped = vec![false; images.len()];
+    let mut groups = Vec::new();
+
+    for lead in 0..images.len() {
+        if grouped[lead] {
+            continue;
+        }
+        let hash = images[lead].hash;
+        let mut candidates: Vec<usize> = bands
+            .iter()
+            .enumerate()
+            .filter_map(|(band, &(shift, width))| {
+                buckets.get(&(band, band_bits(hash, shift, width)))
+            })
+            .flatten()
+            .copied()
+            .filter(|&other| other > lead && !grouped[other])
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut members = vec![images[lead].index];
+        let mut lowest = 1.0f64;
+
+        for other in candidates {
+            let similarity = similarity(images[lead].hash, images[other].hash);
+            if similarity >= options.threshold {
+                grouped[other] = true;
+                members.push(images[other].index);
+                lowest = lowest.min(similarity);
+            }
+        }
+
+        if members.len() > 1 {
+            let files: Vec<LocalFile> = members.iter().map(|&i| files[i].clone()).collect();
+            let total_size = files.iter().skip(1).map(|f| f.size).sum();
+            groups.push(NearDuplicateGroup {
+                kind: NearDuplicateKind::Image,
+                similarity: lowest,
+                total_size,
+                files,
+            });
+        }
+    }
+
+    groups.sort_by_key(|g| std::cmp::Reverse(g.total_size));
+    groups
+}
+
+fn is_image(extension: &str) -> bool {
+    IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+}
+
+/// Splits the 64 hash bits into one more band than the number of bits two
+/// hashes may differ in at `threshold`. Hashes that similar must then agree
+/// on a whole band, so only images sharing a band are compared, and none are
+/// missed. Returns each band's (shift, width).
+fn bands(threshold: f64) -> Vec<(u32, u32)> {
+    // Small slack so thresholds like 0.90625 keep their exact bit count
+    let max_distance = ((1.0 - threshold) * 64.0 + 1e-9).floor() as u32;
+    if max_distance >= 64 {
+        return vec![(0, 0)]; // Everything matches, so one shared bucket
+    }
+    let count = max_distance + 1;
+    (0..count)
+        .map(|band| {
+            let start = band * 64 / count;
+            (start, (band + 1) * 64 / count - start)
+        })
+        .collect()
+}
+
+fn band_bits(hash: u64, shift: u32, width: u32) -> u64 {
+    if width == 0 {
+        return 0;
+    }
+    (hash >> shift) & (u64::MAX >> (64 - width))
+}
+
+/// Share of the 64 hash bits two images have in common
+fn similarity(a: u64, b: u64) -> f64 {
+    1.0 - (a ^ b).count_ones() as f64 / 64.0
+}
+
+/// 64-bit perceptual hash of the image at `path` and its pixel count
+pub fn image_hash(path: &Path, algorithm: ImageHashAlgorithm) -> Result<(u64, u64), String> {
+    let image = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode {}: {}", path.display(), e))?;
+    let (width, height) = image.dimensions();
+
+    let hash = match algorithm {
+        ImageHashAlgorithm::Average => average_hash(&image),
+        ImageHashAlgorithm::Difference => difference_hash(&image),
+        ImageHashAlgorithm::Perceptual => perceptual_hash(&image),
+    };
+    Ok((hash, width as u64 * height as u64))
+}
+
+fn grayscale(image: &DynamicImage, width: u32, height: u32) -> Vec<f64> {
+    image
+        .resize_exact(width, height, FilterType::Triangle)
+        .to_luma8()
+        .pixels()
+        .map(|p| p.0[0] as f64)
+        .collect()
+}
+
+/// One bit per pixel of an 8x8 thumbnail: brighter than the mean or not
+fn average_hash(image: &DynamicImage) -> u64 {
+    let pixels = grayscale(image, HASH_SIZE as u32, HASH_SIZE as u32);
+    let mean = pixels.iter().sum::<f64>() / pixels.len() as f64;
+    bits(pixels.iter().map(|&p| p > mean))
+}
+
+/// One bit per horizontal pixel pair of a 9x8 thumbnail: getting brighter or not
+fn difference_hash(image: &DynamicImage) -> u64 {
+    let width = HASH_SIZE + 1;
+    let pixels = grayscale(image, width as u32, HASH_SIZE as u32);
+    bits((0..HASH_SIZE).flat_map(|y| {
+        let row = &pixels[y * width..(y + 1) * width];
+        (0..HASH_SIZE).map(move |x| row[x + 1] > row[x])
+    }))
+}
+
+/// One bit per low-frequency DCT coefficient of a 32x32 thumbnail: above the
+/// median or not. The DC term is left out of the median since it only
+/// reflects overall brightness.
+fn perceptual_hash(image: &DynamicImage) -> u64 {
+    let pixels = grayscale(image, DCT_SIZE as u32, DCT_SIZE as u32);
+
+    let cosines: Vec<f64> = (0..HASH_SIZE)
+        .flat_map(|u| {
+            (0..DCT_SIZE)
+                .map(move |x| ((2 * x + 1) as f64 * u as f64 * PI / (2 * DCT_SIZE) as f64).cos())
+        })
+        .collect();
+    let cosine = |u: usize, x: usize| cosines[u * DCT_SIZE + x];
+
+    let mut coefficients = Vec::with_capacity(HASH_SIZE * HASH_SIZE);
+    for v in 0..HASH_SIZE {
+        for u in 0..HASH_SIZE {
+            let mut sum = 0.0;
+            for y in 0..DCT_SIZE {
+                for x in 0..DCT_SIZE {
+                    sum += pixels[y * DCT_SIZE + x] * cosine(u, x) * cosine(v, y);
+                }
+            }
+            coefficients.push(sum);
+        }
+    }
+
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted[sorted.len() / 2];
+    bits(coefficients.iter().map(|&c| c > median))
+}
+
+fn bits(values: impl Iterator<Item = bool>) -> u64 {
+    values
+        .take(64)
+        .fold(0u64, |hash, bit| (hash << 1) | bit as u64)
+}
@@ -0,0 +1,310 @@
+// src-tauri/src/scanners/similar_text.rs
+use super::local_scanner::{one_per_hash, LocalFile, NearDuplicateGroup, NearDuplicateKind};
+use super::progress::{ScanMonitor, ScanPhase};
+use super::workers::WorkerPool;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::Path;
+
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "text", "md", "markdown", "rst", "adoc", "tex", "log", "csv", "tsv", "json", "xml",
+    "yaml", "yml", "toml", "ini", "cfg", "conf", "html", "htm", "css", "scss", "sql", "rs", "py",
+    "js", "jsx", "ts", "tsx", "java", "kt", "scala", "go", "rb", "php", "c", "h", "cc", "cpp",
+    "hpp", "cs", "swift", "sh", "bash", "ps1", "lua", "r", "m", "vue", "svelte",
+];
+
+// Office formats are zip archives; these members hold their text
+const OFFICE_TEXT_MEMBERS: &[(&str, &[&str])] = &[
+    ("docx", &["word/document.xml"]),
+    ("xlsx", &["xl/sharedStrings.xml"]),
+    ("pptx", &["ppt/slides/"]),
+    ("odt", &["content.xml"]),
+    ("ods", &["content.xml"]),
+    ("odp", &["content.xml"]),
+];
+
+// Text read from each file; enough to fingerprint without loading huge logs
+const MAX_TEXT_BYTES: u64 = 4 * 1024 * 1024;
+
+// Words per shingle, and how many a file needs before it is worth comparing
+const SHINGLE_WORDS: usize = 3;
+const MIN_WORDS: usize = 20;
+
+// 128 MinHash values, split into 16 bands of 8 for locality-sensitive hashing.
+// Files sharing any band are compared; at 90% similarity a pair shares one
+// with near certainty, at 50% only a few percent of pairs do.
+const SIGNATURE_SIZE: usize = 128;
+const BAND_ROWS: usize = 8;
+
+/// Settings for the optional near-duplicate text pass
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TextSimilarity {
+    pub threshold: f64, // Estimated share of shared word runs, 0 to 1, for two files to be grouped
+}
+
+impl Default for TextSimilarity {
+    fn default() -> Self {
+        TextSimilarity { threshold: 0.9 }
+    }
+}
+
+impl TextSimilarity {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(format!(
+                "Text similarity threshold must be between 0 and 1, got {}",
+                self.threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct Fingerprint {
+    index: usize,
+    signature: Vec<u64>,
+}
+
+/// Groups text files, source code, CSVs and office documents whose contents
+/// are mostly the same, using MinHash over runs of words. Each group keeps
+/// the most recently modified file first. Reading runs on `workers` threads
+/// and stops early if `monitor` is cancelled.
+pub fn find_similar_text(
+    files: &[LocalFile],
+    options: &TextSimilarity,
+    workers: usize,
+    monitor: &ScanMonitor,
+) -> Vec<NearDuplicateGroup> {
+    let pool = WorkerPool::new(workers, "text");
+    monitor.set_phase(ScanPhase::Hashing);
+
+    let candidates = one_per_hash(files, |f| f.size > 0 && is_text_like(&f.extension));
+    monitor.will_hash(
+        candidates
+            .iter()
+            .map(|&i| files[i].size.min(MAX_TEXT_BYTES))
+            .sum(),
+    );
+
+    let permutations = permutations();
+    let mut fingerprints: Vec<Fingerprint> = pool.filter_map(&candidates, |&i| {
+        if monitor.is_cancelled() {
+            return None;
+        }
+        let path = Path::new(&files[i].path);
+        let text = read_text(path, &files[i].extension);
+        monitor.bytes_hashed(files[i].size.min(MAX_TEXT_BYTES), path);
+        let signature = minhash(&shingles(&text.ok()?)?, &permutations);
+        Some(Fingerprint {
+            index: i,
+            signature,
+        })
+    });
+    if monitor.is_cancelled() {
+        return Vec::new();
+    }
+
+    // Newest files first, so each group is led by the latest edit
+    fingerprints.sort_by(|a, b| {
+        files[b.index]
+            .modified_time
+            .cmp(&files[a.index].modified_time)
+            .then(a.index.cmp(&b.index))
+    });
+
+    let mut bands: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (position, fingerprint) in fingerprints.iter().enumerate() {
+        for (band, rows) in fingerprint.signature.chunks(BAND_ROWS).enumerate() {
+            bands
+                .entry((band, band_hash(rows)))
+                .or_default()
+                .push(position);
+        }
+    }
+
+    let mut grouped = vec![false; fingerprints.len()];
+    let mut groups = Vec::new();
+
+    for lead in 0..fingerprints.len() {
+        if grouped[lead] {
+            continue;
+        }
+        let signature = &fingerprints[lead].signature;
+        let mut candidates: Vec<usize> = signature
+            .chunks(BAND_ROWS)
+            .enumerate()
+            .filter_map(|(band, rows)| bands.get(&(band, band_hash(rows))))
+            .flatten()
+            .copied()
+            .filter(|&other| other > lead && !grouped[other])
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut members = vec![fingerprints[lead].index];
+        let mut lowest = 1.0f64;
+        for other in candidates {
+            let similarity = estimated_similarity(signature, &fingerprints[other].signature);
+            if similarity >= options.threshold {
+                grouped[other] = true;
+                members.push(fingerprints[other].index);
+                lowest = lowest.min(similarity);
+            }
+        }
+
+        if members.len() > 1 {
+            let files: Vec<LocalFile> = members.iter().map(|&i| files[i].clone()).collect();
+            let total_size = files.iter().skip(1).map(|f| f.size).sum();
+            groups.push(NearDuplicateGroup {
+                kind: NearDuplicateKind::Text,
+                similarity: lowest,
+                total_size,
+                files,
+            });
+        }
+    }
+
+    groups.sort_by_key(|g| std::cmp::Reverse(g.total_size));
+    groups
+}
+
+fn is_text_like(extension: &str) -> bool {
+    let extension = extension.to_lowercase();
+    TEXT_EXTENSIONS.contains(&extension.as_str())
+        || OFFICE_TEXT_MEMBERS.iter().any(|(ext, _)| *ext == extension)
+}
+
+/// Plain text of a file, pulling the document text out of office formats
+fn read_text(path: &Path, extension: &str) -> Result<String, String> {
+    let extension = extension.to_lowercase();
+    if let Some((_, members)) = OFFICE_TEXT_MEMBERS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+    {
+        return read_office_text(path, members);
+    }
+
+    let file =
+        fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut bytes = Vec::new();
+    file.take(MAX_TEXT_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    // A NUL byte early on means a binary file behind a text extension
+    if bytes.iter().take(8192).any(|&b| b == 0) {
+        return Err(format!("{} is not a text file", path.display()));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Text of the XML members starting with any of `members`, tags stripped
+fn read_office_text(path: &Path, members: &[&str]) -> Result<String, String> {
+    let file =
+        fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.ends_with(".xml") && members.iter().any(|m| name.starts_with(m)))
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+
+    let mut text = String::new();
+    for name in names {
+        let mut xml = String::new();
+        archive
+            .by_name(&name)
+            .map_err(|e| format!("Failed to read {} in {}: {}", name, path.display(), e))?
+            .take(MAX_TEXT_BYTES)
+            .read_to_string(&mut xml)
+            .map_err(|e| format!("Failed to read {} in {}: {}", name, path.display(), e))?;
+        strip_tags(&xml, &mut text);
+    }
+    Ok(text)
+}
+
+fn strip_tags(xml: &str, text: &mut String) {
+    let mut in_tag = false;
+    for c in xml.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Hashes of every run of `SHINGLE_WORDS` consecutive words. None when the
+/// text is too short to compare meaningfully.
+fn shingles(text: &str) -> Option<HashSet<u64>> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    Some(
+        words
+            .windows(SHINGLE_WORDS)
+            .map(|window| {
+                let mut hasher = DefaultHasher::new();
+                window.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect(),
+    )
+}
+
+/// Fixed multipliers and offsets for the MinHash permutations, from splitmix64
+fn permutations() -> Vec<(u64, u64)> {
+    let mut state = 0x5354_4143_4b42_5552u64;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    (0..SIGNATURE_SIZE).map(|_| (next() | 1, next())).collect()
+}
+
+fn minhash(shingles: &HashSet<u64>, permutations: &[(u64, u64)]) -> Vec<u64> {
+    permutations
+        .iter()
+        .map(|&(a, b)| {
+            shingles
+                .iter()
+                .map(|&s| s.wrapping_mul(a).wrapping_add(b))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+fn band_hash(rows: &[u64]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rows.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Share of MinHash values two signatures agree on, which estimates the
+/// Jaccard similarity of their shingle sets
+fn estimated_similarity(a: &[u64], b: &[u64]) -> f64 {
+    let matching = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    matching as f64 / a.len() as f64
+}
@@ -0,0 +1,287 @@
+// src-tauri/src/scanners/size_tree.rs
+use super::local_scanner::{is_unused, unused_cutoff, FolderStats, LocalFile};
+use super::temp_files::TempClassifier;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+#[cfg(feature = "desktop")]
+use tauri::{command, State};
+
+// Scanned roots whose full tree stays in memory for get_subtree
+const MAX_STORED_TREES: usize = 8;
+
+/// How much of the directory tree a scan or `get_subtree` returns
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TreeOptions {
+    pub depth: usize,   // Levels of folders below the returned node
+    pub min_share: f64, // Folders smaller than this share of their parent are folded into `other_size`
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        TreeOptions {
+            depth: 2,
+            min_share: 0.01,
+        }
+    }
+}
+
+impl TreeOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.min_share) {
+            return Err(format!(
+                "Tree minimum share must be between 0 and 1, got {}",
+                self.min_share
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Bytes in a folder that a Burn Score category counts as bloat
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BloatBytes {
+    pub duplicates: u64, // Every copy but the one kept
+    pub near_duplicates: u64,
+    pub versioned: u64, // Older versions of a document
+    pub temporary: u64,
+    pub stale: u64, // Not accessed in 6+ months
+}
+
+impl BloatBytes {
+    fn add(&mut self, other: &BloatBytes) {
+        self.duplicates += other.duplicates;
+        self.near_duplicates += other.near_duplicates;
+        self.versioned += other.versioned;
+        self.temporary += other.temporary;
+        self.stale += other.stale;
+    }
+}
+
+/// A folder with the totals of everything below it
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DirNode {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub file_count: usize,
+    pub own_size: u64, // Files directly in this folder
+    pub bloat: BloatBytes,
+    pub children: Vec<DirNode>, // Largest first
+    pub other_size: u64,        // Subfolders left out for being below `min_share`
+    pub other_count: usize,
+    pub has_more: bool, // Subfolders were cut off by depth; fetch them with `get_subtree`
+}
+
+impl DirNode {
+    /// A copy of this node with `options.depth` levels of children, small
+    /// ones folded into `other_size`
+    pub fn view(&self, options: &TreeOptions) -> DirNode {
+        let mut node = self.without_children();
+        if options.depth == 0 {
+            node.has_more = !self.children.is_empty();
+            return node;
+        }
+
+        let threshold = (self.size as f64 * options.min_share) as u64;
+        let child_options = TreeOptions {
+            depth: options.depth - 1,
+            ..options.clone()
+        };
+        for child in &self.children {
+            if child.size < threshold {
+                node.other_size += child.size;
+                node.other_count += 1;
+            } else {
+                node.children.push(child.view(&child_options));
+            }
+        }
+        node
+    }
+
+    fn without_children(&self) -> DirNode {
+        DirNode {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            size: self.size,
+            file_count: self.file_count,
+            own_size: self.own_size,
+            bloat: self.bloat.clone(),
+            children: Vec::new(),
+            other_size: 0,
+            other_count: 0,
+            has_more: false,
+        }
+    }
+
+    fn find(&self, path: &Path) -> Option<&DirNode> {
+        if Path::new(&self.path) == path {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find(|child| path.starts_with(&child.path))
+            .and_then(|child| child.find(path))
+    }
+}
+
+/// Sums the files of a scan rooted at `root` into a folder tree, marking the
+/// bytes each category of `stats` counts as bloat
+pub fn build_tree(root: &Path, files: &[LocalFile], stats: &FolderStats) -> DirNode {
+    let bloat_paths = BloatPaths::from_stats(stats);
+    let classifier = TempClassifier::new();
+    let cutoff = unused_cutoff();
+    let mut folders: HashMap<PathBuf, DirNode> = HashMap::new();
+
+    for file in files {
+        let path = Path::new(&file.path);
+        let mut bloat = BloatBytes::default();
+        if bloat_paths.duplicates.contains(file.path.as_str()) {
+            bloat.duplicates = file.size;
+        }
+        if bloat_paths.near_duplicates.contains(file.path.as_str()) {
+            bloat.near_duplicates = file.size;
+        }
+        if bloat_paths.versioned.contains(file.path.as_str()) {
+            bloat.versioned = file.size;
+        }
+        if classifier.classify(path).is_some() {
+            bloat.temporary = file.size;
+        }
+        if is_unused(file, cutoff) {
+            bloat.stale = file.size;
+        }
+
+        let mut first = true;
+        for folder in path.ancestors().skip(1) {
+            if !folder.starts_with(root) {
+                break;
+            }
+            let node = folders.entry(folder.to_path_buf()).or_default();
+            node.size += file.size;
+            node.file_count += 1;
+            node.bloat.add(&bloat);
+            if first {
+                node.own_size += file.size;
+                first = false;
+            }
+        }
+    }
+
+    // Attach every folder to its parent, deepest first so children are complete
+    let mut paths: Vec<PathBuf> = folders.keys().cloned().collect();
+    paths.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+    for path in paths {
+        let Some(mut node) = folders.remove(&path) else {
+            continue;
+        };
+        node.name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        node.path = path.to_string_lossy().to_string();
+        node.children.sort_by_key(|c| std::cmp::Reverse(c.size));
+
+        match path.parent().filter(|_| path != root) {
+            Some(parent) => folders
+                .entry(parent.to_path_buf())
+                .or_default()
+                .children
+                .push(node),
+            None => return node,
+        }
+    }
+
+    // Nothing was found below the root
+    DirNode {
+        name: root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: root.to_string_lossy().to_string(),
+        ..DirNode::default()
+    }
+}
+
+struct BloatPaths<'a> {
+    duplicates: HashSet<&'a str>,
+    near_duplicates: HashSet<&'a str>,
+    versioned: HashSet<&'a str>,
+}
+
+impl<'a> BloatPaths<'a> {
+    fn from_stats(stats: &'a FolderStats) -> Self {
+        BloatPaths {
+            duplicates: stats
+                .duplicates
+                .iter()
+                .flat_map(|g| g.files.iter().skip(1))
+                .map(|f| f.path.as_str())
+                .collect(),
+            near_duplicates: stats
+                .near_duplicates
+                .iter()
+                .flat_map(|g| g.files.iter().skip(1))
+                .map(|f| f.path.as_str())
+                .collect(),
+            versioned: stats
+                .versioned
+                .iter()
+                .flat_map(|c| c.older.iter())
+                .map(|f| f.path.as_str())
+                .collect(),
+        }
+    }
+}
+
+/// Full trees of the last `MAX_STORED_TREES` scanned roots, kept so the UI
+/// can expand folders on demand. A scan replaces the trees it covers.
+#[derive(Default)]
+pub struct SizeTrees {
+    trees: Mutex<VecDeque<(String, DirNode)>>, // Oldest first
+}
+
+impl SizeTrees {
+    /// Remembers the tree of the latest scan of `root`
+    pub fn store(&self, root: String, tree: DirNode) -> Result<(), String> {
+        let mut trees = self.lock()?;
+        trees.retain(|(stored, _)| !Path::new(stored).starts_with(&root));
+        trees.push_back((root, tree));
+        while trees.len() > MAX_STORED_TREES {
+            trees.pop_front();
+        }
+        Ok(())
+    }
+
+    /// The folder at `path` inside the latest scan that covers it
+    pub fn subtree(&self, path: &str, options: &TreeOptions) -> Result<DirNode, String> {
+        options.validate()?;
+        let path = Path::new(path);
+        let trees = self.lock()?;
+        trees
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.len())
+            .and_then(|(_, tree)| tree.find(path))
+            .map(|node| node.view(options))
+            .ok_or_else(|| format!("No scanned folder tree contains {}", path.display()))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, VecDeque<(String, DirNode)>>, String> {
+        self.trees
+            .lock()
+            .map_err(|_| "Folder tree store is unavailable".to_string())
+    }
+}
+
+#[cfg(feature = "desktop")]
+#[command]
+pub async fn get_subtree(
+    path: String,
+    options: Option<TreeOptions>,
+    trees: State<'_, SizeTrees>,
+) -> Result<DirNode, String> {
+    trees.subtree(&path, &options.unwrap_or_default())
+}
@@ -0,0 +1,246 @@
+// src-tauri/src/scanners/temp_files.rs
+use super::local_scanner::LocalFile;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+// Temporary files kept in FolderStats, largest first
+const TEMPORARY_SAMPLE_SIZE: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TempKind {
+    TempFile,     // *.tmp, *.temp and OS temp folders
+    Backup,       // Editor backups ending in ~
+    EditorSwap,   // Vim .swp files
+    OsMetadata,   // .DS_Store, Thumbs.db, AppleDouble ._ files
+    CrashDump,    // Core dumps, minidumps, JVM crash logs
+    BrowserCache, // Cache folders inside browser profiles
+    PackageCache, // npm, cargo, pip, yarn, gradle and go download caches
+    AppCache,     // Anything else under the user cache directory or an app's data folder
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemporaryFile {
+    pub path: String,
+    pub size: u64,
+    pub kind: TempKind,
+}
+
+/// Temp and cache files found by a local scan. Everything here is recreated
+/// on demand, so it can be deleted without losing data.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TemporarySummary {
+    pub count: usize,
+    pub total_size: u64,
+    pub size_by_kind: HashMap<TempKind, u64>,
+    pub largest: Vec<TemporaryFile>,
+}
+
+const OS_METADATA_FILES: &[&str] = &[".ds_store", "thumbs.db", "ehthumbs.db"];
+const TEMP_EXTENSIONS: &[&str] = &["tmp", "temp"];
+const SWAP_EXTENSIONS: &[&str] = &["swp", "swo", "swn"];
+const CRASH_DUMP_EXTENSIONS: &[&str] = &["dmp", "mdmp", "crash"];
+
+// Folder names browsers and Electron apps keep their HTTP and GPU caches in
+const CACHE_DIRECTORIES: &[&str] = &[
+    "Cache",
+    "cache2",
+    "Code Cache",
+    "GPUCache",
+    "ShaderCache",
+    "CacheStorage",
+];
+
+// Profile folders that mark a cache directory as belonging to a browser
+const BROWSER_DIRECTORIES: &[&str] = &[
+    "google-chrome",
+    "chromium",
+    "Chrome",
+    "BraveSoftware",
+    "Microsoft Edge",
+    "microsoft-edge",
+    "vivaldi",
+    "opera",
+    ".mozilla",
+    "Firefox",
+    "Safari",
+];
+
+// Runs of folder names that hold package-manager downloads
+const PACKAGE_CACHES: &[&[&str]] = &[
+    &[".npm", "_cacache"],
+    &["npm-cache"],
+    &[".cargo", "registry", "cache"],
+    &[".gradle", "caches"],
+    &[".cache", "pip"],
+    &["pip", "cache"],
+    &[".cache", "yarn"],
+    &["Yarn", "Cache"],
+    &[".yarn", "cache"],
+    &[".pnpm-store"],
+    &["go", "pkg", "mod", "cache"],
+    &[".nuget", "packages"],
+];
+
+// Where desktop and Electron apps keep their data, Cache folders included
+const APP_DATA_DIRECTORIES: &[&str] = &[".config", "Application Support", "AppData"];
+
+// Dot-directories walked for temp and cache files even when hidden files are skipped
+const HIDDEN_CACHE_ROOTS: &[&str] = &[
+    ".cache",
+    ".config",
+    ".npm",
+    ".cargo",
+    ".gradle",
+    ".yarn",
+    ".pnpm-store",
+    ".nuget",
+    ".mozilla",
+];
+
+const TEMP_DIRECTORIES: &[&[&str]] = &[&["AppData", "Local", "Temp"]];
+
+/// Recognizes temp and cache files by name and location
+#[derive(Clone)]
+pub struct TempClassifier {
+    cache_dir: Option<PathBuf>,
+}
+
+impl TempClassifier {
+    pub fn new() -> Self {
+        // On Windows the cache directory is all of AppData\Local, which holds real data too
+        let cache_dir = if cfg!(windows) {
+            None
+        } else {
+            dirs::cache_dir().and_then(|dir| dir.canonicalize().ok())
+        };
+        TempClassifier { cache_dir }
+    }
+
+    pub fn classify(&self, path: &Path) -> Option<TempKind> {
+        let name = path.file_name()?.to_str()?;
+        let lower = name.to_lowercase();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+
+        if OS_METADATA_FILES.contains(&lower.as_str()) || name.starts_with("._") {
+            return Some(TempKind::OsMetadata);
+        }
+        if name.ends_with('~') {
+            return Some(TempKind::Backup);
+        }
+        if SWAP_EXTENSIONS.contains(&extension.as_str()) {
+            return Some(TempKind::EditorSwap);
+        }
+        if is_crash_dump(&lower, &extension) {
+            return Some(TempKind::CrashDump);
+        }
+        if TEMP_EXTENSIONS.contains(&extension.as_str()) {
+            return Some(TempKind::TempFile);
+        }
+
+        let folders: Vec<&str> = path
+            .parent()?
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        if PACKAGE_CACHES.iter().any(|run| contains_run(&folders, run)) {
+            return Some(TempKind::PackageCache);
+        }
+        if let Some(kind) = cache_owner(&folders) {
+            return Some(kind);
+        }
+        if TEMP_DIRECTORIES
+            .iter()
+            .any(|run| contains_run(&folders, run))
+        {
+            return Some(TempKind::TempFile);
+        }
+        if self
+            .cache_dir
+            .as_ref()
+            .is_some_and(|dir| path.starts_with(dir))
+        {
+            return Some(TempKind::AppCache);
+        }
+
+        None
+    }
+
+    /// Totals the temp and cache files among `files`
+    pub fn summarize(&self, files: &[LocalFile]) -> TemporarySummary {
+        let mut summary = TemporarySummary::default();
+        let mut found = Vec::new();
+
+        for file in files {
+            let Some(kind) = self.classify(Path::new(&file.path)) else {
+                continue;
+            };
+            summary.count += 1;
+            summary.total_size += file.size;
+            *summary.size_by_kind.entry(kind).or_insert(0) += file.size;
+            found.push(TemporaryFile {
+                path: file.path.clone(),
+                size: file.size,
+                kind,
+            });
+        }
+
+        found.sort_by_key(|f| std::cmp::Reverse(f.size));
+        found.truncate(TEMPORARY_SAMPLE_SIZE);
+        summary.largest = found;
+        summary
+    }
+}
+
+impl Default for TempClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_crash_dump(name: &str, extension: &str) -> bool {
+    CRASH_DUMP_EXTENSIONS.contains(&extension)
+        || name == "core"
+        || name
+            .strip_prefix("core.")
+            .is_some_and(|pid| !pid.is_empty() && pid.chars().all(|c| c.is_ascii_digit()))
+        || (name.starts_with("hs_err_pid") && extension == "log")
+}
+
+/// Whether a hidden folder is worth walking for temp and cache files
+pub fn may_hold_temp_files(name: &str) -> bool {
+    HIDDEN_CACHE_ROOTS.contains(&name)
+}
+
+/// The kind of cache a `Cache`-style folder in `folders` holds. A bare
+/// `Cache` folder only counts inside a browser profile or an app data folder.
+fn cache_owner(folders: &[&str]) -> Option<TempKind> {
+    let mut kind = None;
+    for (i, folder) in folders.iter().enumerate() {
+        if !CACHE_DIRECTORIES.contains(folder) {
+            continue;
+        }
+        let owners = &folders[..i];
+        if owners.iter().any(|f| BROWSER_DIRECTORIES.contains(f)) {
+            return Some(TempKind::BrowserCache);
+        }
+        if owners.iter().any(|f| APP_DATA_DIRECTORIES.contains(f)) {
+            kind = Some(TempKind::AppCache);
+        }
+    }
+    kind
+}
+
+/// Whether `run` appears as consecutive folders in `folders`
+fn contains_run(folders: &[&str], run: &[&str]) -> bool {
+    folders.windows(run.len()).any(|window| window == run)
+}
@@ -0,0 +1,253 @@
+// src-tauri/src/scanners/versions.rs
+use super::drive_scanner::DriveFile;
+use super::local_scanner::LocalFile;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+// Words people add to a file name to mark another revision of it
+const VERSION_WORDS: &[&str] = &[
+    "final", "old", "new", "real", "latest", "draft", "updated", "edited", "revised",
+];
+
+// Words that announce a version number, as in "v 2" or "version 3"
+const VERSION_PREFIXES: &[&str] = &["v", "ver", "version", "rev"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionedFile {
+    pub path: String, // Local path, or the Drive link for Drive files
+    pub name: String,
+    pub size: u64,
+    pub modified_time: DateTime<Utc>,
+}
+
+/// Files in one folder whose names differ only by version markers such as
+/// "v2", "copy", "(1)", a date, "final" or "old"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionCluster {
+    pub folder: String,
+    pub base_name: String,         // The name with every version marker removed
+    pub canonical: VersionedFile,  // Most recently modified, the one to keep
+    pub older: Vec<VersionedFile>, // Newest first
+    pub wasted_size: u64,          // Total size of the older versions
+}
+
+pub fn local_version_clusters(files: &[LocalFile]) -> Vec<VersionCluster> {
+    let candidates = files.iter().map(|file| {
+        let folder = Path::new(&file.path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let version = VersionedFile {
+            path: file.path.clone(),
+            name: file.name.clone(),
+            size: file.size,
+            modified_time: file.modified_time,
+        };
+        (folder, version)
+    });
+    cluster_versions(candidates)
+}
+
+pub fn drive_version_clusters(files: &[DriveFile]) -> Vec<VersionCluster> {
+    let candidates = files.iter().map(|file| {
+        // Drive files can sit in several folders; the first is where they were created
+        let folder = file.parents.first().cloned().unwrap_or_default();
+        let version = VersionedFile {
+            path: format!("https://drive.google.com/open?id={}", file.id),
+            name: file.name.clone(),
+            size: file.size.unwrap_or(0).max(0) as u64,
+            modified_time: DateTime::parse_from_rfc3339(&file.modified_time)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_default(),
+        };
+        (folder, version)
+    });
+    cluster_versions(candidates)
+}
+
+/// Groups files by folder and marker-free name, keeping groups where at least
+/// one name carried a version marker. Largest waste first.
+fn cluster_versions(
+    candidates: impl Iterator<Item = (String, VersionedFile)>,
+) -> Vec<VersionCluster> {
+    let mut groups: HashMap<(String, String), (Vec<VersionedFile>, bool)> = HashMap::new();
+
+    for (folder, file) in candidates {
+        let Some((base_name, had_marker)) = base_name(&file.name) else {
+            continue;
+        };
+        let group = groups.entry((folder, base_name)).or_default();
+        group.0.push(file);
+        group.1 |= had_marker;
+    }
+
+    let mut clusters: Vec<VersionCluster> = groups
+        .into_iter()
+        .filter(|(_, (files, had_marker))| files.len() > 1 && *had_marker)
+        .map(|((folder, base_name), (mut files, _))| {
+            files.sort_by(|a, b| {
+                b.modified_time
+                    .cmp(&a.modified_time)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            let canonical = files.remove(0);
+            let wasted_size = files.iter().map(|f| f.size).sum();
+            VersionCluster {
+                folder,
+                base_name,
+                canonical,
+                older: files,
+                wasted_size,
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.wasted_size
+            .cmp(&a.wasted_size)
+            .then_with(|| a.canonical.path.cmp(&b.canonical.path))
+    });
+    clusters
+}
+
+struct Token {
+    text: String,
+    bracketed: bool, // Inside () or []
+}
+
+/// The lowercase name with version markers stripped, and whether any were
+/// found. None when nothing but markers is left.
+fn base_name(name: &str) -> Option<(String, bool)> {
+    let path = Path::new(name);
+    let stem = path.file_stem()?.to_str()?.to_lowercase();
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+
+    let tokens = tokenize(&stem);
+    let mut kept: Vec<&str> = Vec::new();
+    let mut had_marker = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let marker_len = marker_length(&tokens[i..]);
+        if marker_len > 0 {
+            had_marker = true;
+            i += marker_len;
+        } else {
+            kept.push(&tokens[i].text);
+            i += 1;
+        }
+    }
+
+    if kept.is_empty() {
+        return None;
+    }
+    let mut base = kept.join(" ");
+    if let Some(extension) = extension {
+        base.push('.');
+        base.push_str(&extension);
+    }
+    Some((base, had_marker))
+}
+
+/// How many tokens from the start of `tokens` make up a version marker
+fn marker_length(tokens: &[Token]) -> usize {
+    let token = &tokens[0];
+    let text = token.text.as_str();
+    let next_is_number = tokens.get(1).is_some_and(|t| is_number(&t.text));
+
+    if is_date(tokens) {
+        return 3;
+    }
+    if is_compact_date(text) {
+        return 1;
+    }
+    // "(1)" and "[2]" from downloads and copy dialogs
+    if token.bracketed && is_number(text) {
+        return 1;
+    }
+    // "Copy of report" and "report copy 2"
+    if text == "copy" {
+        if tokens.get(1).is_some_and(|t| t.text == "of") {
+            return 2;
+        }
+        return if next_is_number { 2 } else { 1 };
+    }
+    if VERSION_WORDS.contains(&text) {
+        return 1;
+    }
+    if VERSION_PREFIXES.contains(&text) && next_is_number {
+        return 2;
+    }
+    if VERSION_PREFIXES
+        .iter()
+        .any(|prefix| text.strip_prefix(prefix).is_some_and(is_number))
+    {
+        return 1;
+    }
+    0
+}
+
+/// Year-month-day or day-month-year, split into three tokens
+fn is_date(tokens: &[Token]) -> bool {
+    let [a, b, c] = match tokens {
+        [a, b, c, ..] => [a.text.as_str(), b.text.as_str(), c.text.as_str()],
+        _ => return false,
+    };
+    if !is_number(a) || !is_number(b) || !is_number(c) {
+        return false;
+    }
+    let short = |s: &str| s.len() <= 2;
+    (is_year(a) && short(b) && short(c)) || (short(a) && short(b) && is_year(c))
+}
+
+/// YYYYMMDD
+fn is_compact_date(text: &str) -> bool {
+    text.len() == 8 && is_number(text) && is_year(&text[..4]) && {
+        let month: u32 = text[4..6].parse().unwrap_or(0);
+        let day: u32 = text[6..].parse().unwrap_or(0);
+        (1..=12).contains(&month) && (1..=31).contains(&day)
+    }
+}
+
+fn is_year(text: &str) -> bool {
+    text.len() == 4 && (text.starts_with("19") || text.starts_with("20")) && is_number(text)
+}
+
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
+fn tokenize(stem: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in stem.chars() {
+        let separator = matches!(c, ' ' | '_' | '-' | '.' | ',' | '(' | ')' | '[' | ']');
+        if separator && !current.is_empty() {
+            tokens.push(Token {
+                text: std::mem::take(&mut current),
+                bracketed: depth > 0,
+            });
+        }
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if !separator => current.push(c),
+            _ => {}
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(Token {
+            text: current,
+            bracketed: depth > 0,
+        });
+    }
+
+    tokens
+}
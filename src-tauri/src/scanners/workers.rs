@@ -0,0 +1,134 @@
+// src-tauri/src/scanners/workers.rs
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+// Concurrent readers beyond this just make a spinning disk seek back and forth
+const HDD_WORKERS: usize = 2;
+const UNKNOWN_STORAGE_WORKERS: usize = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageKind {
+    #[default]
+    Auto, // Detect from the device holding the scanned path
+    Ssd,
+    Hdd,
+}
+
+impl FromStr for StorageKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(StorageKind::Auto),
+            "ssd" => Ok(StorageKind::Ssd),
+            "hdd" => Ok(StorageKind::Hdd),
+            _ => Err(format!("Unknown storage kind: {}", s)),
+        }
+    }
+}
+
+/// How many blocking workers a scan may use for walking and hashing
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WorkerConfig {
+    pub storage: StorageKind,
+    pub max_workers: Option<usize>, // Caps the storage-based default
+}
+
+impl WorkerConfig {
+    /// Number of workers to use for a scan of `path`
+    pub fn workers_for(&self, path: &Path) -> usize {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        let storage = match self.storage {
+            StorageKind::Auto => detect_storage(path),
+            kind => Some(kind),
+        };
+        let workers = match storage {
+            Some(StorageKind::Hdd) => HDD_WORKERS,
+            Some(_) => cores,
+            None => UNKNOWN_STORAGE_WORKERS.min(cores),
+        };
+
+        self.max_workers
+            .map_or(workers, |max| workers.min(max))
+            .max(1)
+    }
+}
+
+/// Threads for one stage of a scan. When they can't be started the stage
+/// runs serially on the calling thread instead.
+pub struct WorkerPool {
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl WorkerPool {
+    /// Starts `workers` threads; `task` names the stage in the fallback warning
+    pub fn new(workers: usize, task: &str) -> Self {
+        match rayon::ThreadPoolBuilder::new().num_threads(workers).build() {
+            Ok(pool) => WorkerPool { pool: Some(pool) },
+            Err(e) => {
+                eprintln!("Failed to start {} workers, running serially: {}", task, e);
+                WorkerPool { pool: None }
+            }
+        }
+    }
+
+    /// Applies `f` to every item, keeping the results in order
+    pub fn map<I, T, F>(&self, items: &[I], f: F) -> Vec<T>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&I) -> T + Sync + Send,
+    {
+        match &self.pool {
+            Some(pool) => pool.install(|| items.par_iter().map(f).collect()),
+            None => items.iter().map(f).collect(),
+        }
+    }
+
+    /// Applies `f` to every item, keeping the `Some` results in order
+    pub fn filter_map<I, T, F>(&self, items: &[I], f: F) -> Vec<T>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&I) -> Option<T> + Sync + Send,
+    {
+        match &self.pool {
+            Some(pool) => pool.install(|| items.par_iter().filter_map(f).collect()),
+            None => items.iter().filter_map(f).collect(),
+        }
+    }
+}
+
+/// Reads the kernel's rotational flag for the block device behind `path`
+#[cfg(target_os = "linux")]
+fn detect_storage(path: &Path) -> Option<StorageKind> {
+    use std::os::unix::fs::MetadataExt;
+
+    let dev = std::fs::metadata(path).ok()?.dev();
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+    let device = Path::new("/sys/dev/block").join(format!("{}:{}", major, minor));
+
+    // Partitions keep the queue settings on their parent disk
+    let rotational = std::fs::read_to_string(device.join("queue/rotational"))
+        .or_else(|_| std::fs::read_to_string(device.join("../queue/rotational")))
+        .ok()?;
+
+    match rotational.trim() {
+        "1" => Some(StorageKind::Hdd),
+        "0" => Some(StorageKind::Ssd),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect_storage(_path: &Path) -> Option<StorageKind> {
+    None
+}
@@ -1,5 +1,6 @@
 // src/api/stackburn-api.ts
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 
 // Extend window type for Tauri
 declare global {
@@ -46,9 +47,25 @@ export interface DriveScanResult {
   file_types: Record<string, number>;
   largest_files: DriveFile[];
   oldest_files: DriveFile[];
+  versioned: VersionCluster[];
   scan_timestamp: string;
 }
 
+export interface VersionedFile {
+  path: string;
+  name: string;
+  size: number;
+  modified_time: string;
+}
+
+export interface VersionCluster {
+  folder: string;
+  base_name: string;
+  canonical: VersionedFile;
+  older: VersionedFile[];
+  wasted_size: number;
+}
+
 export interface LocalFile {
   path: string;
   name: string;
@@ -58,6 +75,8 @@ export interface LocalFile {
   accessed_time?: string;
   is_hidden: boolean;
   hash?: string;
+  allocated_size?: number;
+  category: string;
 }
 
 export interface DuplicateGroup {
@@ -66,15 +85,256 @@ export interface DuplicateGroup {
   files: LocalFile[];
 }
 
+export type ArchiveFormat = 'zip' | 'tar' | 'tar_gz' | 'tar_zst' | 'gz';
+
+export interface ArchiveMember {
+  path: string;
+  size: number;
+  hash: string;
+}
+
+export interface ArchiveListing {
+  path: string;
+  format: ArchiveFormat;
+  unpacked_size: number;
+  members: ArchiveMember[];
+}
+
+export interface Extraction {
+  folder: string;
+  matched_files: number;
+  matched_size: number;
+  complete: boolean;
+  exclusive: boolean;
+}
+
+export interface ArchiveReport {
+  path: string;
+  format: ArchiveFormat;
+  size: number;
+  member_count: number;
+  unpacked_size: number;
+  extractions: Extraction[];
+  redundant: string[];
+  redundant_size: number;
+}
+
+export interface HardlinkSet {
+  device: number;
+  inode: number;
+  size: number;
+  link_count: number;
+  paths: string[];
+}
+
+export type NearDuplicateKind = 'image' | 'text';
+
+export interface NearDuplicateGroup {
+  kind: NearDuplicateKind;
+  similarity: number;
+  total_size: number;
+  files: LocalFile[];
+}
+
+export interface ScanChanges {
+  previous_scan?: string;
+  added_count: number;
+  removed_count: number;
+  modified_count: number;
+  added: string[];
+  removed: string[];
+  modified: string[];
+  files_hashed: number;
+  hashes_reused: number;
+}
+
+export type TempKind =
+  | 'temp_file'
+  | 'backup'
+  | 'editor_swap'
+  | 'os_metadata'
+  | 'crash_dump'
+  | 'browser_cache'
+  | 'package_cache'
+  | 'app_cache';
+
+export interface TemporaryFile {
+  path: string;
+  size: number;
+  kind: TempKind;
+}
+
+export interface TemporarySummary {
+  count: number;
+  total_size: number;
+  size_by_kind: Partial<Record<TempKind, number>>;
+  largest: TemporaryFile[];
+}
+
+export interface BrokenSymlink {
+  path: string;
+  target: string;
+}
+
+// empty_directories lists the top of each chain of empty folders
+export interface StructuralJunk {
+  empty_directories: string[];
+  empty_directory_count: number;
+  zero_byte_files: string[];
+  broken_symlinks: BrokenSymlink[];
+}
+
+export type ScanErrorKind = 'permission' | 'io' | 'too_long' | 'non_utf8_name';
+
+export interface ScanError {
+  path: string;
+  kind: ScanErrorKind;
+  message: string;
+}
+
+export interface ErrorCounts {
+  permission: number;
+  io: number;
+  too_long: number;
+  non_utf8_name: number;
+}
+
 export interface FolderStats {
   total_files: number;
   total_directories: number;
   total_size: number;
-  file_types: Record<string, number>;
+  disk_usage: number;
+  file_types: Record<string, number>; // By category, as for Drive
+  extensions: Record<string, number>;
   largest_files: LocalFile[];
   duplicates: DuplicateGroup[];
+  near_duplicates: NearDuplicateGroup[];
+  archives: ArchiveReport[];
   unused_files: LocalFile[];
+  hardlinks: HardlinkSet[];
+  temporary: TemporarySummary;
+  versioned: VersionCluster[];
+  structural_junk: StructuralJunk;
+  tree?: DirNode;
+  scan_timestamp: string;
+  changes?: ScanChanges;
+  incomplete: boolean;
+  errors: ScanError[]; // The first 500; error_counts covers every one
+  error_counts: ErrorCounts;
+}
+
+export interface DuplicateScanResult {
+  groups: DuplicateGroup[];
+  incomplete: boolean;
+}
+
+export type StorageKind = 'auto' | 'ssd' | 'hdd';
+
+export interface WorkerConfig {
+  storage?: StorageKind;
+  max_workers?: number;
+}
+
+// Patterns use gitignore syntax; omitted fields keep the default profile,
+// which excludes node_modules, .git, target, build, dist and system folders
+export interface ScanRules {
+  include?: string[];
+  exclude?: string[];
+  respect_ignore_files?: boolean;
+  max_depth?: number;
+  min_size?: number;
+  max_size?: number;
+  include_hidden?: boolean;
+  follow_symlinks?: boolean;
+  cross_filesystems?: boolean;
+}
+
+export type ImageHashAlgorithm = 'average' | 'difference' | 'perceptual';
+
+export interface ImageSimilarity {
+  algorithm?: ImageHashAlgorithm;
+  threshold?: number;
+}
+
+export interface TextSimilarity {
+  threshold?: number;
+}
+
+export interface TreeOptions {
+  depth?: number;
+  min_share?: number;
+}
+
+export interface ScanOptions {
+  full_rescan?: boolean;
+  workers?: WorkerConfig;
+  rules?: ScanRules;
+  inspect_archives?: boolean;
+  similar_images?: ImageSimilarity;
+  similar_text?: TextSimilarity;
+  tree?: TreeOptions;
+}
+
+export interface BloatBytes {
+  duplicates: number;
+  near_duplicates: number;
+  versioned: number;
+  temporary: number;
+  stale: number;
+}
+
+// Folders smaller than min_share of their parent are summed into other_size;
+// has_more means get_subtree can return deeper levels
+export interface DirNode {
+  name: string;
+  path: string;
+  size: number;
+  file_count: number;
+  own_size: number;
+  bloat: BloatBytes;
+  children: DirNode[];
+  other_size: number;
+  other_count: number;
+  has_more: boolean;
+}
+
+export interface BuildArtifact {
+  path: string;
+  kind: string;
+  size_bytes: number;
+  disk_usage_bytes: number;
+  file_count: number;
+  project_root?: string;
+  project_type?: string;
+  project_last_modified?: string;
+  regenerable: boolean;
+}
+
+export interface ArtifactScanResult {
+  root: string;
+  artifacts: BuildArtifact[];
+  total_size_bytes: number;
+  disk_usage_bytes: number;
+  regenerable_size_bytes: number;
   scan_timestamp: string;
+  incomplete: boolean;
+  errors: ScanError[];
+  error_counts: ErrorCounts;
+}
+
+export type ScanPhase = 'walking' | 'hashing' | 'fetching' | 'analyzing' | 'done';
+
+export interface ScanProgress {
+  scan_id: string;
+  phase: ScanPhase;
+  files_seen: number;
+  bytes_hashed: number;
+  bytes_to_hash: number;
+  repos_fetched: number;
+  current_path?: string;
+  elapsed_seconds: number;
+  eta_seconds?: number;
+  cancelled: boolean;
 }
 
 export interface GitHubRepo {
@@ -112,12 +372,30 @@ export interface GitHubScanResult {
   largest_repos: GitHubRepo[];
   orphaned_branches: Record<string, any[]>;
   scan_timestamp: string;
+  incomplete: boolean;
+}
+
+export type ItemSource = 'Local' | 'GitHub' | 'Drive';
+
+export interface ScannedItem {
+  id: string;
+  source: ItemSource;
+  name: string;
+  location: string;
+  size_bytes: number;
+  created_at?: string;
+  modified_at?: string;
+  accessed_at?: string;
+  fingerprint?: string;
+  owner?: string;
 }
 
 export interface BurnScoreInput {
-  drive_data?: any;
-  local_data?: any;
-  github_data?: any;
+  drive_data?: DriveScanResult;
+  local_data?: FolderStats;
+  github_data?: GitHubScanResult;
+  artifact_data?: ArtifactScanResult;
+  items?: ScannedItem[];
 }
 
 export interface CategoryStats {
@@ -125,6 +403,8 @@ export interface CategoryStats {
   total_size_gb: number;
   percentage_of_total: number;
   items: string[];
+  local_paths: string[]; // Every file on this machine counted here
+  regenerable: boolean;
 }
 
 export interface FileCategories {
@@ -134,6 +414,10 @@ export interface FileCategories {
   archived: CategoryStats;
   large_unused: CategoryStats;
   temporary: CategoryStats;
+  build_artifacts: CategoryStats;
+  near_duplicates: CategoryStats;
+  unpacked_archives: CategoryStats;
+  structural_junk: CategoryStats;
 }
 
 export interface Recommendation {
@@ -152,10 +436,149 @@ export interface BurnScoreResult {
   total_files_scanned: number;
   recommendations: Recommendation[];
   file_categories: FileCategories;
+  file_types: Record<string, Record<string, number>>; // Source label -> category -> files
+  coverage_percent?: number; // Share of local paths that could be read
   potential_savings_gb: number;
   calculated_at: string;
 }
 
+export type ScanKind = 'local' | 'github' | 'burn_score';
+
+export interface ScanSummary {
+  id: number;
+  kind: ScanKind;
+  target: string;
+  scanned_at: string;
+  total_items: number;
+  total_bytes: number;
+  burn_score?: number;
+}
+
+export interface ScanRecord extends ScanSummary {
+  result: FolderStats | GitHubScanResult | BurnScoreResult;
+}
+
+export interface ScanDelta {
+  from: ScanSummary;
+  to: ScanSummary;
+  elapsed_days: number;
+  items_delta: number;
+  size_delta_bytes: number;
+  score_delta?: number;
+}
+
+export interface ActionFailure {
+  path: string;
+  error: string;
+}
+
+export interface QuarantinedItem {
+  original_path: string;
+  stored_name: string;
+  size_bytes: number;
+  modified_time?: string;
+  restored: boolean;
+}
+
+export interface QuarantineBatch {
+  id: string;
+  created_at: string;
+  reason?: string;
+  items: QuarantinedItem[];
+  total_bytes: number;
+  expires_at: string;
+}
+
+export interface QuarantineOutcome {
+  batch?: QuarantineBatch;
+  failures: ActionFailure[];
+}
+
+export interface RestoreOutcome {
+  batch_id: string;
+  restored: number;
+  failures: ActionFailure[];
+}
+
+export interface PurgeOutcome {
+  batches_purged: number;
+  bytes_freed: number;
+}
+
+export interface QuarantineSettings {
+  retention_days: number;
+}
+
+export interface TrashedItem {
+  original_path: string;
+  trashed_path: string;
+  info_path: string;
+  size_bytes: number;
+  deleted_at: string;
+}
+
+export interface TrashOutcome {
+  trashed: TrashedItem[];
+  failures: ActionFailure[];
+}
+
+export interface TrashRestoreOutcome {
+  restored: string[];
+  failures: ActionFailure[];
+}
+
+export type LinkMode = 'auto' | 'hardlink' | 'reflink';
+
+export interface DedupeOutcome {
+  hardlinked: number;
+  reflinked: number;
+  already_linked: number;
+  bytes_reclaimed: number;
+  failures: ActionFailure[];
+}
+
+export interface EmptyDirOutcome {
+  removed: string[];
+  failures: ActionFailure[];
+}
+
+export type PlanAction = 'delete' | 'move' | 'hardlink' | 'archive';
+
+export interface PlannedAction {
+  action: PlanAction;
+  path: string;
+  target?: string;
+  keep?: string;
+  size_bytes: number;
+  modified_time?: string;
+  hash?: string;
+  reason: string;
+}
+
+export interface CleanupPlan {
+  version: number;
+  created_at: string;
+  source: string;
+  total_bytes: number;
+  actions: PlannedAction[];
+}
+
+export interface PlanOptions {
+  duplicates?: PlanAction; // Local scans only; burn scores don't say which copy is kept
+  unused?: PlanAction;
+  temporary?: PlanAction; // Temp and cache files, recreated on demand
+  destination?: string;
+}
+
+export interface PlanReport {
+  dry_run: boolean;
+  applied: number;
+  bytes_reclaimed: number;
+  drifted: ActionFailure[];
+  failures: ActionFailure[];
+  quarantine_batch?: string;
+}
+
 // Google Drive Scanner API
 export const driveScanner = {
   /**
@@ -191,10 +614,15 @@ export const driveScanner = {
 // Local Scanner API
 export const localScanner = {
   /**
-   * Scan a directory for files
+   * Scan a directory for files, rehashing only files changed since the last
+   * scan unless options.full_rescan is set
    */
-  async scanDirectory(path: string): Promise<FolderStats | null> {
-    return safeInvoke<FolderStats>('scan_directory', { path });
+  async scanDirectory(
+    path: string,
+    options?: ScanOptions,
+    scanId?: string
+  ): Promise<FolderStats | null> {
+    return safeInvoke<FolderStats>('scan_directory', { path, options, scanId });
   },
 
   /**
@@ -207,8 +635,43 @@ export const localScanner = {
   /**
    * Detect duplicate files across multiple paths
    */
-  async detectDuplicates(paths: string[]): Promise<DuplicateGroup[] | null> {
-    return safeInvoke<DuplicateGroup[]>('detect_duplicates', { paths });
+  async detectDuplicates(
+    paths: string[],
+    rules?: ScanRules,
+    workers?: WorkerConfig,
+    scanId?: string
+  ): Promise<DuplicateScanResult | null> {
+    return safeInvoke<DuplicateScanResult>('detect_duplicates', {
+      paths,
+      rules,
+      workers,
+      scanId,
+    });
+  },
+
+  /**
+   * Find node_modules, target, build and dist folders and the projects that own them
+   */
+  async scanBuildArtifacts(
+    path: string,
+    workers?: WorkerConfig,
+    scanId?: string
+  ): Promise<ArtifactScanResult | null> {
+    return safeInvoke<ArtifactScanResult>('scan_build_artifacts', { path, workers, scanId });
+  },
+
+  /**
+   * List and hash the files inside a zip, tar, tar.gz, tar.zst or gz archive
+   */
+  async listArchiveMembers(path: string): Promise<ArchiveListing | null> {
+    return safeInvoke<ArchiveListing>('list_archive_members', { path });
+  },
+
+  /**
+   * Expand a folder from the tree of the latest scan that covered it
+   */
+  async getSubtree(path: string, options?: TreeOptions): Promise<DirNode | null> {
+    return safeInvoke<DirNode>('get_subtree', { path, options });
   },
 };
 
@@ -224,15 +687,35 @@ export const githubScanner = {
   /**
    * Scan all repositories
    */
-  async scanRepositories(): Promise<GitHubScanResult | null> {
-    return safeInvoke<GitHubScanResult>('scan_repositories');
+  async scanRepositories(scanId?: string): Promise<GitHubScanResult | null> {
+    return safeInvoke<GitHubScanResult>('scan_repositories', { scanId });
   },
 
   /**
    * Get only stale repositories
    */
-  async getStaleRepos(): Promise<GitHubRepo[] | null> {
-    return safeInvoke<GitHubRepo[]>('get_stale_repos');
+  async getStaleRepos(scanId?: string): Promise<GitHubRepo[] | null> {
+    return safeInvoke<GitHubRepo[]>('get_stale_repos', { scanId });
+  },
+};
+
+// Scan Progress API
+export const scanProgress = {
+  /**
+   * Subscribe to progress of every running scan; filter by scan_id to follow one
+   */
+  async onProgress(handler: (progress: ScanProgress) => void): Promise<UnlistenFn | null> {
+    if (typeof window === 'undefined' || !window.__TAURI__) {
+      return null;
+    }
+    return listen<ScanProgress>('scan-progress', (event) => handler(event.payload));
+  },
+
+  /**
+   * Stop a running scan; it resolves with a partial result flagged incomplete
+   */
+  async cancelScan(scanId: string): Promise<boolean | null> {
+    return safeInvoke<boolean>('cancel_scan', { scanId });
   },
 };
 
@@ -260,6 +743,149 @@ export const burnScoreEngine = {
   },
 };
 
+// Scan History API
+export const scanHistory = {
+  /**
+   * List past scans, newest first
+   */
+  async listScans(kind?: ScanKind, limit?: number): Promise<ScanSummary[] | null> {
+    return safeInvoke<ScanSummary[]>('list_scans', { kind, limit });
+  },
+
+  /**
+   * Fetch one stored scan with its full result
+   */
+  async getScan(id: number): Promise<ScanRecord | null> {
+    return safeInvoke<ScanRecord>('get_scan', { id });
+  },
+
+  /**
+   * Compare two scans of the same kind
+   */
+  async compareScans(fromId: number, toId: number): Promise<ScanDelta | null> {
+    return safeInvoke<ScanDelta>('compare_scans', { fromId, toId });
+  },
+};
+
+// Cleanup API
+export const cleanup = {
+  /**
+   * Move files into quarantine as one restorable batch
+   */
+  async quarantineFiles(paths: string[], reason?: string): Promise<QuarantineOutcome | null> {
+    return safeInvoke<QuarantineOutcome>('quarantine_files', { paths, reason });
+  },
+
+  /**
+   * Quarantine every copy except the first file of each group
+   */
+  async quarantineDuplicates(groups: DuplicateGroup[]): Promise<QuarantineOutcome | null> {
+    return safeInvoke<QuarantineOutcome>('quarantine_duplicates', { groups });
+  },
+
+  /**
+   * List quarantined batches, newest first
+   */
+  async listQuarantine(): Promise<QuarantineBatch[] | null> {
+    return safeInvoke<QuarantineBatch[]>('list_quarantine');
+  },
+
+  /**
+   * Move a batch's files back to their original paths
+   */
+  async restoreBatch(batchId: string): Promise<RestoreOutcome | null> {
+    return safeInvoke<RestoreOutcome>('restore_quarantine_batch', { batchId });
+  },
+
+  /**
+   * Permanently delete expired batches, or those older than the given age
+   */
+  async purgeQuarantine(olderThanDays?: number): Promise<PurgeOutcome | null> {
+    return safeInvoke<PurgeOutcome>('purge_quarantine', { olderThanDays });
+  },
+
+  /**
+   * Set how many days new batches are kept before they are purged
+   */
+  async setRetention(retentionDays: number): Promise<QuarantineSettings | null> {
+    return safeInvoke<QuarantineSettings>('set_quarantine_retention', { retentionDays });
+  },
+
+  /**
+   * Move files or folders to the desktop trash
+   */
+  async trashFiles(paths: string[]): Promise<TrashOutcome | null> {
+    return safeInvoke<TrashOutcome>('trash_files', { paths });
+  },
+
+  /**
+   * Trash every copy except the first file of each group
+   */
+  async trashDuplicates(groups: DuplicateGroup[]): Promise<TrashOutcome | null> {
+    return safeInvoke<TrashOutcome>('trash_duplicates', { groups });
+  },
+
+  /**
+   * Items StackBurn trashed that are still in the trash, newest first
+   */
+  async listTrashed(): Promise<TrashedItem[] | null> {
+    return safeInvoke<TrashedItem[]>('list_trashed');
+  },
+
+  /**
+   * Move trashed items back to their original paths
+   */
+  async restoreTrashed(trashedPaths: string[]): Promise<TrashRestoreOutcome | null> {
+    return safeInvoke<TrashRestoreOutcome>('restore_trashed', { trashedPaths });
+  },
+
+  /**
+   * Replace duplicate copies with hardlinks or reflinks to the first copy, keeping every path
+   */
+  async dedupeDuplicates(groups: DuplicateGroup[], mode?: LinkMode): Promise<DedupeOutcome | null> {
+    return safeInvoke<DedupeOutcome>('dedupe_duplicates', { groups, mode });
+  },
+
+  /**
+   * Remove folders that hold nothing but empty folders, innermost first
+   */
+  async removeEmptyDirs(paths: string[]): Promise<EmptyDirOutcome | null> {
+    return safeInvoke<EmptyDirOutcome>('remove_empty_dirs', { paths });
+  },
+
+  /**
+   * Propose cleanup actions from a local scan or a burn score
+   */
+  async createPlan(
+    source: { localData?: FolderStats; burnScore?: BurnScoreResult },
+    options?: PlanOptions
+  ): Promise<CleanupPlan | null> {
+    return safeInvoke<CleanupPlan>('create_cleanup_plan', { ...source, options });
+  },
+
+  /**
+   * Save a plan for review; paths ending in .toml are written as TOML
+   */
+  async savePlan(plan: CleanupPlan, path: string): Promise<void | null> {
+    return safeInvoke<void>('save_cleanup_plan', { plan, path });
+  },
+
+  async loadPlan(path: string): Promise<CleanupPlan | null> {
+    return safeInvoke<CleanupPlan>('load_cleanup_plan', { path });
+  },
+
+  /**
+   * Apply a plan, skipping files that changed since it was made
+   */
+  async applyPlan(
+    plan: CleanupPlan,
+    dryRun?: boolean,
+    useTrash?: boolean
+  ): Promise<PlanReport | null> {
+    return safeInvoke<PlanReport>('apply_cleanup_plan', { plan, dryRun, useTrash });
+  },
+};
+
 // Utility functions for frontend integration
 export const stackBurnUtils = {
   /**